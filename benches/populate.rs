@@ -0,0 +1,60 @@
+//! Times `Maze::populate` and counts the heap allocations it makes.
+//!
+//! Run with `cargo bench --bench populate`.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Instant,
+};
+
+use labyrinth::Maze;
+use rand::{rngs::SmallRng, SeedableRng};
+
+// counts every allocation made through it before handing it over to the system allocator
+struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+const RUNS: u32 = 10;
+
+fn main() {
+    for &size in &[101, 501, 1001] {
+        // creating the maze allocates its grids, which isn't what is being measured
+        let mazes: Vec<Maze> = (0..RUNS).map(|_| Maze::new(size, size)).collect();
+
+        let before = ALLOCATIONS.load(Ordering::Relaxed);
+        let start = Instant::now();
+        for (seed, mut maze) in (0..).zip(mazes) {
+            maze.populate(&mut SmallRng::seed_from_u64(seed));
+        }
+        let elapsed = start.elapsed();
+        let allocations = ALLOCATIONS.load(Ordering::Relaxed) - before;
+
+        println!(
+            "populate {0}x{0}: {1:>10.2?} per maze, {2:>8} allocations per maze",
+            size,
+            elapsed / RUNS,
+            allocations / RUNS as usize
+        );
+    }
+}
@@ -0,0 +1,156 @@
+//! A minimal animated GIF writer, just enough for saving the frames of a maze being carved.
+
+use std::{
+    collections::HashMap,
+    io::{self, Write},
+};
+
+use rgb::RGB8;
+
+// codes in a GIF can't be longer than 12 bits
+const MAX_CODE_SIZE: u8 = 12;
+
+/// Writes an animated GIF which loops forever, every frame is a full image of palette indices
+/// in row order and is shown for `delay_cs` hundredths of a second.
+///
+/// The palette can hold at most 256 colours.
+pub fn write<W, I>(
+    mut w: W,
+    width: u16,
+    height: u16,
+    palette: &[RGB8],
+    frames: I,
+    delay_cs: u16,
+) -> io::Result<()>
+where
+    W: Write,
+    I: IntoIterator<Item = Vec<u8>>,
+{
+    // the colour table has to be a power of two in size, with at least two entries
+    let bits = palette_bits(palette.len());
+
+    w.write_all(b"GIF89a")?;
+    w.write_all(&width.to_le_bytes())?;
+    w.write_all(&height.to_le_bytes())?;
+    // a global colour table follows, with `bits` bits of colour resolution
+    w.write_all(&[0x80 | ((bits - 1) << 4) | (bits - 1), 0, 0])?;
+    for i in 0..1 << bits {
+        let RGB8 { r, g, b } = palette.get(i).copied().unwrap_or_default();
+        w.write_all(&[r, g, b])?;
+    }
+
+    // the netscape extension makes the animation loop, 0 repeats means forever
+    w.write_all(b"\x21\xFF\x0BNETSCAPE2.0\x03\x01\x00\x00\x00")?;
+
+    for frame in frames {
+        // the graphic control extension holds the delay before the next frame
+        let [delay_lo, delay_hi] = delay_cs.to_le_bytes();
+        w.write_all(&[0x21, 0xF9, 0x04, 0x00, delay_lo, delay_hi, 0x00, 0x00])?;
+
+        // every frame covers the whole image and uses the global colour table
+        w.write_all(&[0x2C, 0, 0, 0, 0])?;
+        w.write_all(&width.to_le_bytes())?;
+        w.write_all(&height.to_le_bytes())?;
+        w.write_all(&[0x00])?;
+
+        let min_code_size = bits.max(2);
+        w.write_all(&[min_code_size])?;
+        for block in lzw_compress(&frame, min_code_size).chunks(0xFF) {
+            w.write_all(&[block.len() as u8])?;
+            w.write_all(block)?;
+        }
+        w.write_all(&[0x00])?;
+    }
+
+    w.write_all(&[0x3B])
+}
+
+fn palette_bits(len: usize) -> u8 {
+    let mut bits = 1;
+    while 1 << bits < len && bits < 8 {
+        bits += 1;
+    }
+    bits
+}
+
+// the variable length LZW used by GIF, the code size grows as the dictionary fills up
+// and the dictionary is cleared once it reaches the 12 bit limit
+fn lzw_compress(indices: &[u8], min_code_size: u8) -> Vec<u8> {
+    let clear = 1_u16 << min_code_size;
+    let end = clear + 1;
+
+    let mut output = BitWriter::default();
+    let mut dictionary: HashMap<(u16, u8), u16> = HashMap::new();
+    let mut code_size = min_code_size + 1;
+    let mut next_code = end + 1;
+
+    output.write(clear, code_size);
+
+    let mut indices = indices.iter().copied();
+    let Some(first) = indices.next() else {
+        output.write(end, code_size);
+        return output.finish();
+    };
+
+    let mut prefix = u16::from(first);
+    for index in indices {
+        if let Some(&code) = dictionary.get(&(prefix, index)) {
+            prefix = code;
+            continue;
+        }
+
+        output.write(prefix, code_size);
+        // the decoder is one code behind, so only grow once it has seen the code that fills
+        // the size
+        if next_code > (1 << code_size) - 1 && code_size < MAX_CODE_SIZE {
+            code_size += 1;
+        }
+
+        if next_code < 1 << MAX_CODE_SIZE {
+            dictionary.insert((prefix, index), next_code);
+            next_code += 1;
+        } else {
+            output.write(clear, code_size);
+            dictionary.clear();
+            code_size = min_code_size + 1;
+            next_code = end + 1;
+        }
+
+        prefix = u16::from(index);
+    }
+
+    output.write(prefix, code_size);
+    if next_code > (1 << code_size) - 1 && code_size < MAX_CODE_SIZE {
+        code_size += 1;
+    }
+    output.write(end, code_size);
+
+    output.finish()
+}
+
+// packs codes into bytes least significant bit first
+#[derive(Debug, Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    buffer: u32,
+    bits: u8,
+}
+
+impl BitWriter {
+    fn write(&mut self, code: u16, size: u8) {
+        self.buffer |= u32::from(code) << self.bits;
+        self.bits += size;
+        while self.bits >= 8 {
+            self.bytes.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.bits -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.bits > 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+        self.bytes
+    }
+}
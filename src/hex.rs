@@ -0,0 +1,399 @@
+//! Mazes on a grid of hexagons.
+//!
+//! Hexes are addressed by axial coordinates `(q, r)`, where `r` is the row counting down from the
+//! top and `q` increases to the east. The grid is laid out as a rectangle of pointy topped hexes
+//! with the odd rows pushed half a hex to the right, so the first hex of row `r` has
+//! `q = -(r / 2)`.
+
+use std::{collections::VecDeque, ffi::OsStr, fmt::Write, fs, path::Path};
+
+use anyhow::Context;
+use grid::Grid;
+use rand::{prelude::SliceRandom, Rng};
+
+use crate::{maze::hex_colour, TileState};
+
+/// A maze on a rectangular grid of hexagons, each of which has up to six neighbours.
+///
+/// The start is the top left hex and the end is the bottom right hex.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct HexMaze {
+    width: u32,
+    height: u32,
+    // a set bit means the side of the hex facing `HexDirection::ALL[bit]` is still a wall
+    walls: Grid<u8>,
+}
+
+impl HexMaze {
+    /// Creates a maze of `width` hexes across and `height` down with every wall in place.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either the width or height is zero.
+    pub fn new(width: u32, height: u32) -> Self {
+        assert!(
+            width > 0 && height > 0,
+            "Cannot create a {}x{} hex maze, the width and height must both be at least 1.",
+            width,
+            height
+        );
+
+        Self {
+            width,
+            height,
+            walls: Grid::init(width as usize, height as usize, ALL_WALLS),
+        }
+    }
+
+    /// Generates the maze using a randomized depth first search.
+    pub fn populate<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        let mut visited = Grid::init(self.width as usize, self.height as usize, false);
+        let start = self.offset_to_axial(
+            rng.gen_range(0..self.width) as usize,
+            rng.gen_range(0..self.height) as usize,
+        );
+        self.set_visited(&mut visited, start);
+
+        let mut stack = vec![start];
+        while let Some(&hex) = stack.last() {
+            let unvisited: Vec<_> = HexDirection::ALL
+                .iter()
+                .filter_map(|&direction| {
+                    let neighbour = self.neighbour(hex, direction)?;
+                    (!self.is_visited(&visited, neighbour)).then_some((neighbour, direction))
+                })
+                .collect();
+
+            if let Some(&(next, direction)) = unvisited.choose(rng) {
+                self.carve(hex, direction);
+                self.set_visited(&mut visited, next);
+                stack.push(next);
+            } else {
+                stack.pop();
+            }
+        }
+    }
+
+    /// Whether the hex is inside the grid.
+    pub fn contains(&self, (q, r): (i32, i32)) -> bool {
+        self.axial_to_offset((q, r)).is_some()
+    }
+
+    /// The neighbouring hex in a direction, `None` if it would be outside of the grid.
+    pub fn neighbour(&self, (q, r): (i32, i32), direction: HexDirection) -> Option<(i32, i32)> {
+        if !self.contains((q, r)) {
+            return None;
+        }
+
+        let (dq, dr) = direction.offset();
+        let neighbour = (q + dq, r + dr);
+        self.contains(neighbour).then_some(neighbour)
+    }
+
+    /// Every neighbouring hex inside the grid, whether or not there is a wall in the way.
+    ///
+    /// ```
+    /// use labyrinth::HexMaze;
+    ///
+    /// let maze = HexMaze::new(4, 3);
+    /// // the corner hexes are cut off on several sides
+    /// assert!(maze.neighbours(maze.start()).len() < 6);
+    ///
+    /// for r in 0..3 {
+    ///     for q in -(r / 2)..4 - (r / 2) {
+    ///         let neighbours = maze.neighbours((q, r));
+    ///         assert!(!neighbours.is_empty() && neighbours.len() <= 6);
+    ///         assert!(neighbours.iter().all(|&hex| maze.contains(hex)));
+    ///     }
+    /// }
+    /// ```
+    pub fn neighbours(&self, hex: (i32, i32)) -> Vec<(i32, i32)> {
+        HexDirection::ALL
+            .iter()
+            .filter_map(|&direction| self.neighbour(hex, direction))
+            .collect()
+    }
+
+    /// The neighbouring hexes which can be reached without going through a wall.
+    pub fn open_neighbours(&self, hex: (i32, i32)) -> Vec<(i32, i32)> {
+        HexDirection::ALL
+            .iter()
+            .filter(|&&direction| self.is_open(hex, direction))
+            .filter_map(|&direction| self.neighbour(hex, direction))
+            .collect()
+    }
+
+    /// Whether the side of a hex facing `direction` has been carved away.
+    pub fn is_open(&self, hex: (i32, i32), direction: HexDirection) -> bool {
+        self.wall_bits(hex)
+            .is_some_and(|bits| bits & direction.bit() == 0)
+    }
+
+    /// The hex at the top left corner of the grid.
+    pub fn start(&self) -> (i32, i32) {
+        self.offset_to_axial(0, 0)
+    }
+
+    /// The hex at the bottom right corner of the grid.
+    pub fn end(&self) -> (i32, i32) {
+        self.offset_to_axial(self.width as usize - 1, self.height as usize - 1)
+    }
+
+    /// Finds the shortest path from the start to the end using a breadth first search.
+    ///
+    /// The path includes both the start and end hexes, `None` is returned
+    /// if the end can't be reached.
+    ///
+    /// ```
+    /// use labyrinth::HexMaze;
+    /// use rand::{rngs::SmallRng, SeedableRng};
+    ///
+    /// let mut maze = HexMaze::new(6, 5);
+    /// assert_eq!(maze.solve_bfs(), None);
+    ///
+    /// maze.populate(&mut SmallRng::seed_from_u64(4));
+    /// let path = maze.solve_bfs().unwrap();
+    /// assert_eq!(path.first(), Some(&maze.start()));
+    /// assert_eq!(path.last(), Some(&maze.end()));
+    /// // each step goes through a gap in the walls to the next hex
+    /// assert!(path
+    ///     .windows(2)
+    ///     .all(|step| maze.open_neighbours(step[0]).contains(&step[1])));
+    /// ```
+    pub fn solve_bfs(&self) -> Option<Vec<(i32, i32)>> {
+        let (start, end) = (self.start(), self.end());
+
+        // the hex each hex was first reached from, the start points at itself
+        let mut previous: Grid<Option<(i32, i32)>> =
+            Grid::new(self.width as usize, self.height as usize);
+        let (col, row) = self.axial_to_offset(start)?;
+        *previous.get_mut(col, row)? = Some(start);
+
+        let mut queue = VecDeque::from(vec![start]);
+        while let Some(hex) = queue.pop_front() {
+            if hex == end {
+                break;
+            }
+
+            for neighbour in self.open_neighbours(hex) {
+                let (col, row) = self.axial_to_offset(neighbour)?;
+                if let Some(prev @ None) = previous.get_mut(col, row) {
+                    *prev = Some(hex);
+                    queue.push_back(neighbour);
+                }
+            }
+        }
+
+        // walk back from the end following the previous pointers
+        let mut path = vec![end];
+        let mut hex = end;
+        while hex != start {
+            let (col, row) = self.axial_to_offset(hex)?;
+            hex = previous.get(col, row).copied().flatten()?;
+            path.push(hex);
+        }
+
+        path.reverse();
+        Some(path)
+    }
+
+    /// Saves the maze as an SVG image with hexes 10 units from the centre to each corner,
+    /// see `to_svg`.
+    pub fn save_svg<S: AsRef<OsStr> + ?Sized>(&self, s: &S) -> anyhow::Result<()> {
+        let path = Path::new(s);
+        fs::write(path, self.to_svg(HEX_SVG_SIZE))
+            .with_context(|| format!("Failed to write the SVG to {}.", path.display()))
+    }
+
+    /// Renders the maze as an SVG image with hexes `size` units from the centre to each corner.
+    ///
+    /// The start and end hexes are filled in and the walls are drawn as lines, every wall
+    /// shared by two hexes is only drawn once.
+    pub fn to_svg(&self, size: u32) -> String {
+        let size = f64::from(size);
+        let hex_width = 3_f64.sqrt() * size;
+        let margin = size / 4.0;
+        let width = (hex_width * (f64::from(self.width) + 0.5) + margin * 2.0).ceil();
+        let height = (size * (1.5 * f64::from(self.height - 1) + 2.0) + margin * 2.0).ceil();
+
+        let mut svg = String::new();
+        let _ = writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+            w = width,
+            h = height,
+        );
+        let _ = writeln!(
+            svg,
+            r#"<rect width="{}" height="{}" fill="{}"/>"#,
+            width,
+            height,
+            hex_colour(&TileState::Empty)
+        );
+
+        let corners = |(col, row): (usize, usize)| {
+            let cx = margin + hex_width * (col as f64 + 0.5 + (row % 2) as f64 * 0.5);
+            let cy = margin + size * (1.0 + 1.5 * row as f64);
+            let mut corners = [(0.0, 0.0); 6];
+            for (i, corner) in corners.iter_mut().enumerate() {
+                // corner i and i + 1 bound the side facing HexDirection::ALL[i]
+                let angle = (60.0 * i as f64 - 30.0).to_radians();
+                *corner = (cx + size * angle.cos(), cy + size * angle.sin());
+            }
+            corners
+        };
+
+        for (hex, tile) in [
+            (self.start(), TileState::Start),
+            (self.end(), TileState::End),
+        ] {
+            if let Some(offset) = self.axial_to_offset(hex) {
+                let points: Vec<_> = corners(offset)
+                    .iter()
+                    .map(|(x, y)| format!("{:.2},{:.2}", x, y))
+                    .collect();
+                let _ = writeln!(
+                    svg,
+                    r#"<polygon points="{}" fill="{}"/>"#,
+                    points.join(" "),
+                    hex_colour(&tile)
+                );
+            }
+        }
+
+        let _ = writeln!(
+            svg,
+            r#"<g stroke="{}" stroke-width="{:.2}" stroke-linecap="round">"#,
+            hex_colour(&TileState::Wall),
+            size / 5.0
+        );
+        for row in 0..self.height as usize {
+            for col in 0..self.width as usize {
+                let hex = self.offset_to_axial(col, row);
+                let corners = corners((col, row));
+                for (i, &direction) in HexDirection::ALL.iter().enumerate() {
+                    // inner walls are drawn from the hex on their west / north side
+                    let owned = self.neighbour(hex, direction).is_none()
+                        || matches!(
+                            direction,
+                            HexDirection::East | HexDirection::SouthEast | HexDirection::SouthWest
+                        );
+                    if owned && !self.is_open(hex, direction) {
+                        let ((x1, y1), (x2, y2)) = (corners[i], corners[(i + 1) % 6]);
+                        let _ = writeln!(
+                            svg,
+                            r#"<line x1="{:.2}" y1="{:.2}" x2="{:.2}" y2="{:.2}"/>"#,
+                            x1, y1, x2, y2
+                        );
+                    }
+                }
+            }
+        }
+        svg.push_str("</g>\n</svg>\n");
+
+        svg
+    }
+
+    // knocks down the wall between a hex and its neighbour, from both sides
+    fn carve(&mut self, hex: (i32, i32), direction: HexDirection) {
+        let Some(neighbour) = self.neighbour(hex, direction) else {
+            return;
+        };
+
+        for (hex, direction) in [(hex, direction), (neighbour, direction.opposite())] {
+            if let Some((col, row)) = self.axial_to_offset(hex) {
+                if let Some(bits) = self.walls.get_mut(col, row) {
+                    *bits &= !direction.bit();
+                }
+            }
+        }
+    }
+
+    fn wall_bits(&self, hex: (i32, i32)) -> Option<u8> {
+        let (col, row) = self.axial_to_offset(hex)?;
+        self.walls.get(col, row).copied()
+    }
+
+    fn is_visited(&self, visited: &Grid<bool>, hex: (i32, i32)) -> bool {
+        self.axial_to_offset(hex)
+            .is_none_or(|(col, row)| visited.get(col, row) == Some(&true))
+    }
+
+    fn set_visited(&self, visited: &mut Grid<bool>, hex: (i32, i32)) {
+        if let Some((col, row)) = self.axial_to_offset(hex) {
+            if let Some(visited) = visited.get_mut(col, row) {
+                *visited = true;
+            }
+        }
+    }
+
+    // the column and row of a hex in the rectangle, `None` if it's outside of the grid
+    fn axial_to_offset(&self, (q, r): (i32, i32)) -> Option<(usize, usize)> {
+        if r < 0 || r >= self.height as i32 {
+            return None;
+        }
+
+        let col = q + r / 2;
+        (0..self.width as i32)
+            .contains(&col)
+            .then_some((col as usize, r as usize))
+    }
+
+    fn offset_to_axial(&self, col: usize, row: usize) -> (i32, i32) {
+        (col as i32 - row as i32 / 2, row as i32)
+    }
+}
+
+/// The six directions from a hex to its neighbours, in clockwise order starting from the east.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum HexDirection {
+    East,
+    SouthEast,
+    SouthWest,
+    West,
+    NorthWest,
+    NorthEast,
+}
+
+impl HexDirection {
+    pub const ALL: [HexDirection; 6] = [
+        HexDirection::East,
+        HexDirection::SouthEast,
+        HexDirection::SouthWest,
+        HexDirection::West,
+        HexDirection::NorthWest,
+        HexDirection::NorthEast,
+    ];
+
+    /// The direction pointing back the way this one came.
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self.index() + 3) % 6]
+    }
+
+    // the change in axial coordinates from taking a step in this direction
+    fn offset(self) -> (i32, i32) {
+        use HexDirection::*;
+        match self {
+            East => (1, 0),
+            SouthEast => (0, 1),
+            SouthWest => (-1, 1),
+            West => (-1, 0),
+            NorthWest => (0, -1),
+            NorthEast => (1, -1),
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    fn bit(self) -> u8 {
+        1 << self.index()
+    }
+}
+
+// every side of a hex walled off
+const ALL_WALLS: u8 = 0b11_1111;
+
+// the distance from the centre of a hex to a corner in SVG units when saving to a file
+const HEX_SVG_SIZE: u32 = 10;
@@ -0,0 +1,356 @@
+//! A minimal baseline JPEG writer, just enough for saving a maze as a small lossy image.
+
+use std::io::{self, Write};
+
+use rgb::RGB8;
+
+// the position in a block of each coefficient, in the zigzag order they are written in
+const ZIGZAG: [usize; 64] = [
+    0, 1, 8, 16, 9, 2, 3, 10, 17, 24, 32, 25, 18, 11, 4, 5, 12, 19, 26, 33, 40, 48, 41, 34, 27, 20,
+    13, 6, 7, 14, 21, 28, 35, 42, 49, 56, 57, 50, 43, 36, 29, 22, 15, 23, 30, 37, 44, 51, 58, 59,
+    52, 45, 38, 31, 39, 46, 53, 60, 61, 54, 47, 55, 62, 63,
+];
+
+// the example quantisation tables from the JPEG spec, which are the ones for quality 50
+const LUMA_QUANTISATION: [u8; 64] = [
+    16, 11, 10, 16, 24, 40, 51, 61, 12, 12, 14, 19, 26, 58, 60, 55, 14, 13, 16, 24, 40, 57, 69, 56,
+    14, 17, 22, 29, 51, 87, 80, 62, 18, 22, 37, 56, 68, 109, 103, 77, 24, 35, 55, 64, 81, 104, 113,
+    92, 49, 64, 78, 87, 103, 121, 120, 101, 72, 92, 95, 98, 112, 100, 103, 99,
+];
+
+const CHROMA_QUANTISATION: [u8; 64] = [
+    17, 18, 24, 47, 99, 99, 99, 99, 18, 21, 26, 66, 99, 99, 99, 99, 24, 26, 56, 99, 99, 99, 99, 99,
+    47, 66, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99,
+    99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99,
+];
+
+// a Huffman table as it is stored in the file: how many codes there are of each length from
+// 1 to 16 bits, then the symbols in order of their codes
+struct HuffmanSpec {
+    counts: [u8; 16],
+    symbols: &'static [u8],
+}
+
+// the typical Huffman tables from the JPEG spec, so no statistics need gathering
+const LUMA_DC: HuffmanSpec = HuffmanSpec {
+    counts: [0, 1, 5, 1, 1, 1, 1, 1, 1, 0, 0, 0, 0, 0, 0, 0],
+    symbols: &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11],
+};
+
+const CHROMA_DC: HuffmanSpec = HuffmanSpec {
+    counts: [0, 3, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 0, 0, 0, 0],
+    symbols: &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11],
+};
+
+const LUMA_AC: HuffmanSpec = HuffmanSpec {
+    counts: [0, 2, 1, 3, 3, 2, 4, 3, 5, 5, 4, 4, 0, 0, 1, 0x7D],
+    symbols: &[
+        0x01, 0x02, 0x03, 0x00, 0x04, 0x11, 0x05, 0x12, 0x21, 0x31, 0x41, 0x06, 0x13, 0x51, 0x61,
+        0x07, 0x22, 0x71, 0x14, 0x32, 0x81, 0x91, 0xA1, 0x08, 0x23, 0x42, 0xB1, 0xC1, 0x15, 0x52,
+        0xD1, 0xF0, 0x24, 0x33, 0x62, 0x72, 0x82, 0x09, 0x0A, 0x16, 0x17, 0x18, 0x19, 0x1A, 0x25,
+        0x26, 0x27, 0x28, 0x29, 0x2A, 0x34, 0x35, 0x36, 0x37, 0x38, 0x39, 0x3A, 0x43, 0x44, 0x45,
+        0x46, 0x47, 0x48, 0x49, 0x4A, 0x53, 0x54, 0x55, 0x56, 0x57, 0x58, 0x59, 0x5A, 0x63, 0x64,
+        0x65, 0x66, 0x67, 0x68, 0x69, 0x6A, 0x73, 0x74, 0x75, 0x76, 0x77, 0x78, 0x79, 0x7A, 0x83,
+        0x84, 0x85, 0x86, 0x87, 0x88, 0x89, 0x8A, 0x92, 0x93, 0x94, 0x95, 0x96, 0x97, 0x98, 0x99,
+        0x9A, 0xA2, 0xA3, 0xA4, 0xA5, 0xA6, 0xA7, 0xA8, 0xA9, 0xAA, 0xB2, 0xB3, 0xB4, 0xB5, 0xB6,
+        0xB7, 0xB8, 0xB9, 0xBA, 0xC2, 0xC3, 0xC4, 0xC5, 0xC6, 0xC7, 0xC8, 0xC9, 0xCA, 0xD2, 0xD3,
+        0xD4, 0xD5, 0xD6, 0xD7, 0xD8, 0xD9, 0xDA, 0xE1, 0xE2, 0xE3, 0xE4, 0xE5, 0xE6, 0xE7, 0xE8,
+        0xE9, 0xEA, 0xF1, 0xF2, 0xF3, 0xF4, 0xF5, 0xF6, 0xF7, 0xF8, 0xF9, 0xFA,
+    ],
+};
+
+const CHROMA_AC: HuffmanSpec = HuffmanSpec {
+    counts: [0, 2, 1, 2, 4, 4, 3, 4, 7, 5, 4, 4, 0, 1, 2, 0x77],
+    symbols: &[
+        0x00, 0x01, 0x02, 0x03, 0x11, 0x04, 0x05, 0x21, 0x31, 0x06, 0x12, 0x41, 0x51, 0x07, 0x61,
+        0x71, 0x13, 0x22, 0x32, 0x81, 0x08, 0x14, 0x42, 0x91, 0xA1, 0xB1, 0xC1, 0x09, 0x23, 0x33,
+        0x52, 0xF0, 0x15, 0x62, 0x72, 0xD1, 0x0A, 0x16, 0x24, 0x34, 0xE1, 0x25, 0xF1, 0x17, 0x18,
+        0x19, 0x1A, 0x26, 0x27, 0x28, 0x29, 0x2A, 0x35, 0x36, 0x37, 0x38, 0x39, 0x3A, 0x43, 0x44,
+        0x45, 0x46, 0x47, 0x48, 0x49, 0x4A, 0x53, 0x54, 0x55, 0x56, 0x57, 0x58, 0x59, 0x5A, 0x63,
+        0x64, 0x65, 0x66, 0x67, 0x68, 0x69, 0x6A, 0x73, 0x74, 0x75, 0x76, 0x77, 0x78, 0x79, 0x7A,
+        0x82, 0x83, 0x84, 0x85, 0x86, 0x87, 0x88, 0x89, 0x8A, 0x92, 0x93, 0x94, 0x95, 0x96, 0x97,
+        0x98, 0x99, 0x9A, 0xA2, 0xA3, 0xA4, 0xA5, 0xA6, 0xA7, 0xA8, 0xA9, 0xAA, 0xB2, 0xB3, 0xB4,
+        0xB5, 0xB6, 0xB7, 0xB8, 0xB9, 0xBA, 0xC2, 0xC3, 0xC4, 0xC5, 0xC6, 0xC7, 0xC8, 0xC9, 0xCA,
+        0xD2, 0xD3, 0xD4, 0xD5, 0xD6, 0xD7, 0xD8, 0xD9, 0xDA, 0xE2, 0xE3, 0xE4, 0xE5, 0xE6, 0xE7,
+        0xE8, 0xE9, 0xEA, 0xF2, 0xF3, 0xF4, 0xF5, 0xF6, 0xF7, 0xF8, 0xF9, 0xFA,
+    ],
+};
+
+/// Writes a baseline JPEG of `pixels` in row order, at a `quality` from 1 to 100 where higher
+/// is bigger and sharper. The colour isn't subsampled, so thin red and green lines survive.
+pub fn write<W: Write>(
+    mut w: W,
+    width: u16,
+    height: u16,
+    pixels: &[RGB8],
+    quality: u8,
+) -> io::Result<()> {
+    let luma_table = scaled_quantisation(&LUMA_QUANTISATION, quality);
+    let chroma_table = scaled_quantisation(&CHROMA_QUANTISATION, quality);
+
+    w.write_all(&[0xFF, 0xD8])?;
+    // a JFIF header with square pixels so viewers don't stretch the maze
+    write_segment(
+        &mut w,
+        0xE0,
+        b"JFIF\x00\x01\x01\x00\x00\x01\x00\x01\x00\x00",
+    )?;
+
+    for (id, table) in [luma_table, chroma_table].iter().enumerate() {
+        let mut dqt = vec![id as u8];
+        dqt.extend(ZIGZAG.iter().map(|&i| table[i]));
+        write_segment(&mut w, 0xDB, &dqt)?;
+    }
+
+    // three components with no subsampling, the luma uses table 0 and the chroma table 1
+    let [height_hi, height_lo] = height.to_be_bytes();
+    let [width_hi, width_lo] = width.to_be_bytes();
+    write_segment(
+        &mut w,
+        0xC0,
+        &[
+            8, height_hi, height_lo, width_hi, width_lo, 3, 1, 0x11, 0, 2, 0x11, 1, 3, 0x11, 1,
+        ],
+    )?;
+
+    // DC tables are class 0 and AC tables class 1
+    for (class_and_id, spec) in [
+        (0x00, &LUMA_DC),
+        (0x10, &LUMA_AC),
+        (0x01, &CHROMA_DC),
+        (0x11, &CHROMA_AC),
+    ] {
+        let mut dht = vec![class_and_id];
+        dht.extend_from_slice(&spec.counts);
+        dht.extend_from_slice(spec.symbols);
+        write_segment(&mut w, 0xC4, &dht)?;
+    }
+
+    write_segment(&mut w, 0xDA, &[3, 1, 0x00, 2, 0x11, 3, 0x11, 0, 63, 0])?;
+
+    let tables = [
+        (
+            &luma_table,
+            huffman_codes(&LUMA_DC),
+            huffman_codes(&LUMA_AC),
+        ),
+        (
+            &chroma_table,
+            huffman_codes(&CHROMA_DC),
+            huffman_codes(&CHROMA_AC),
+        ),
+        (
+            &chroma_table,
+            huffman_codes(&CHROMA_DC),
+            huffman_codes(&CHROMA_AC),
+        ),
+    ];
+    let mut bits = BitWriter::default();
+    let mut previous_dc = [0; 3];
+    let (width, height) = (usize::from(width), usize::from(height));
+    for block_y in (0..height).step_by(8) {
+        for block_x in (0..width).step_by(8) {
+            let blocks = colour_blocks(pixels, width, height, block_x, block_y);
+            for (component, block) in blocks.iter().enumerate() {
+                let (quantisation, dc_codes, ac_codes) = &tables[component];
+                let coefficients = quantise(&forward_dct(block), quantisation);
+                encode_block(
+                    &mut bits,
+                    &coefficients,
+                    &mut previous_dc[component],
+                    dc_codes,
+                    ac_codes,
+                );
+            }
+        }
+    }
+    w.write_all(&bits.finish())?;
+
+    w.write_all(&[0xFF, 0xD9])
+}
+
+fn write_segment<W: Write>(w: &mut W, marker: u8, data: &[u8]) -> io::Result<()> {
+    // the length counts itself but not the marker
+    let length = (data.len() + 2) as u16;
+    w.write_all(&[0xFF, marker])?;
+    w.write_all(&length.to_be_bytes())?;
+    w.write_all(data)
+}
+
+// the usual libjpeg scaling, quality 50 is the table as given and 100 is all ones
+fn scaled_quantisation(table: &[u8; 64], quality: u8) -> [u8; 64] {
+    let quality = u32::from(quality.clamp(1, 100));
+    let scale = if quality < 50 {
+        5000 / quality
+    } else {
+        200 - 2 * quality
+    };
+
+    let mut scaled = [0; 64];
+    for (s, &t) in scaled.iter_mut().zip(table.iter()) {
+        *s = ((u32::from(t) * scale + 50) / 100).clamp(1, 255) as u8;
+    }
+    scaled
+}
+
+// the code and its length in bits for every symbol, the codes are handed out in order of
+// length like the spec's canonical Huffman codes
+fn huffman_codes(spec: &HuffmanSpec) -> [(u16, u8); 256] {
+    let mut codes = [(0, 0); 256];
+    let mut symbols = spec.symbols.iter();
+    let mut code = 0_u16;
+    for (length, &count) in (1..=16).zip(spec.counts.iter()) {
+        for &symbol in symbols.by_ref().take(usize::from(count)) {
+            codes[usize::from(symbol)] = (code, length);
+            code += 1;
+        }
+        code <<= 1;
+    }
+    codes
+}
+
+// the Y, Cb and Cr samples of the 8x8 block with its top left at (x, y), shifted to be centred
+// on 0. blocks hanging off the right or bottom edge repeat the last column or row
+fn colour_blocks(
+    pixels: &[RGB8],
+    width: usize,
+    height: usize,
+    x: usize,
+    y: usize,
+) -> [[f32; 64]; 3] {
+    let (mut luma, mut blue, mut red) = ([0.0; 64], [0.0; 64], [0.0; 64]);
+    let samples = luma.iter_mut().zip(blue.iter_mut()).zip(red.iter_mut());
+    for (i, ((luma, blue), red)) in samples.enumerate() {
+        let (px, py) = ((x + i % 8).min(width - 1), (y + i / 8).min(height - 1));
+        let RGB8 { r, g, b } = pixels.get(py * width + px).copied().unwrap_or_default();
+        let (r, g, b) = (f32::from(r), f32::from(g), f32::from(b));
+
+        *luma = 0.299 * r + 0.587 * g + 0.114 * b - 128.0;
+        *blue = -0.168_736 * r - 0.331_264 * g + 0.5 * b;
+        *red = 0.5 * r - 0.418_688 * g - 0.081_312 * b;
+    }
+    [luma, blue, red]
+}
+
+// the 2D DCT-II done as a 1D transform along the rows then down the columns
+fn forward_dct(block: &[f32; 64]) -> [f32; 64] {
+    let mut cosines = [[0.0; 8]; 8];
+    for (u, row) in cosines.iter_mut().enumerate() {
+        let scale = if u == 0 { 0.5_f32.sqrt() } else { 1.0 };
+        for (x, c) in row.iter_mut().enumerate() {
+            let angle = (2 * x + 1) as f32 * u as f32 * std::f32::consts::PI / 16.0;
+            *c = 0.5 * scale * angle.cos();
+        }
+    }
+
+    let transform = |input: &[f32; 64], stride: usize, step: usize| {
+        let mut output = [0.0; 64];
+        for line in 0..8 {
+            for (u, cosine) in cosines.iter().enumerate() {
+                output[line * step + u * stride] = (0..8)
+                    .map(|x| cosine[x] * input[line * step + x * stride])
+                    .sum();
+            }
+        }
+        output
+    };
+
+    let rows = transform(block, 1, 8);
+    transform(&rows, 8, 1)
+}
+
+fn quantise(coefficients: &[f32; 64], table: &[u8; 64]) -> [i16; 64] {
+    let mut quantised = [0; 64];
+    for ((q, &c), &t) in quantised.iter_mut().zip(coefficients).zip(table) {
+        *q = (c / f32::from(t)).round() as i16;
+    }
+    quantised
+}
+
+// the DC coefficient is coded as the difference from the previous block's, then the AC
+// coefficients as runs of zeros followed by a value, with an end of block code once the
+// rest are all zero
+fn encode_block(
+    bits: &mut BitWriter,
+    coefficients: &[i16; 64],
+    previous_dc: &mut i16,
+    dc_codes: &[(u16, u8); 256],
+    ac_codes: &[(u16, u8); 256],
+) {
+    let dc = coefficients[0];
+    let (size, value) = magnitude(dc - *previous_dc);
+    *previous_dc = dc;
+    bits.write_code(dc_codes[usize::from(size)]);
+    bits.write(value, size);
+
+    let mut zeros = 0;
+    for &i in &ZIGZAG[1..] {
+        let coefficient = coefficients[i];
+        if coefficient == 0 {
+            zeros += 1;
+            continue;
+        }
+
+        // runs longer than 15 zeros are split up with the 16 zero code
+        while zeros > 15 {
+            bits.write_code(ac_codes[0xF0]);
+            zeros -= 16;
+        }
+
+        let (size, value) = magnitude(coefficient);
+        bits.write_code(ac_codes[usize::from(zeros << 4 | size)]);
+        bits.write(value, size);
+        zeros = 0;
+    }
+
+    if zeros > 0 {
+        bits.write_code(ac_codes[0x00]);
+    }
+}
+
+// how many bits a value needs and the bits themselves, negative values are stored one less
+// than their two's complement so the size bits alone tell the sign
+fn magnitude(value: i16) -> (u8, u16) {
+    let size = (16 - value.unsigned_abs().leading_zeros()) as u8;
+    let bits = if value < 0 { value - 1 } else { value } as u16;
+    (size, bits & ((1 << size) - 1))
+}
+
+// packs bits most significant first, putting a zero byte after every 0xFF so it can't be
+// mistaken for a marker
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    buffer: u32,
+    length: u8,
+}
+
+impl BitWriter {
+    fn write_code(&mut self, (code, length): (u16, u8)) {
+        self.write(code, length);
+    }
+
+    fn write(&mut self, bits: u16, length: u8) {
+        self.buffer = self.buffer << length | u32::from(bits);
+        self.length += length;
+
+        while self.length >= 8 {
+            self.length -= 8;
+            let byte = (self.buffer >> self.length) as u8;
+            self.buffer &= (1 << self.length) - 1;
+            self.bytes.push(byte);
+            if byte == 0xFF {
+                self.bytes.push(0x00);
+            }
+        }
+    }
+
+    // the last byte is padded out with one bits
+    fn finish(mut self) -> Vec<u8> {
+        if self.length > 0 {
+            let padding = 8 - self.length;
+            self.write((1 << padding) - 1, padding);
+        }
+        self.bytes
+    }
+}
@@ -0,0 +1,280 @@
+//! A minimal JSON reader / writer, just enough for saving and loading mazes.
+
+use std::{collections::BTreeMap, fmt, iter::Peekable, str::CharIndices};
+
+use anyhow::{anyhow, bail};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Value>),
+    Object(BTreeMap<String, Value>),
+}
+
+impl Value {
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Object(fields) => fields.get(key),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Value]> {
+        match self {
+            Value::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    pub fn as_u64(&self) -> Option<u64> {
+        match *self {
+            Value::Number(n) if n >= 0.0 && n.fract() == 0.0 && n <= u64::MAX as f64 => {
+                Some(n as u64)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Null => write!(f, "null"),
+            Value::Bool(b) => write!(f, "{}", b),
+            Value::Number(n) => write!(f, "{}", n),
+            Value::String(s) => write_string(f, s),
+            Value::Array(values) => {
+                write!(f, "[")?;
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", value)?;
+                }
+                write!(f, "]")
+            }
+            Value::Object(fields) => {
+                write!(f, "{{")?;
+                for (i, (key, value)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write_string(f, key)?;
+                    write!(f, ":{}", value)?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+fn write_string(f: &mut fmt::Formatter, s: &str) -> fmt::Result {
+    write!(f, "\"")?;
+    for c in s.chars() {
+        match c {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\r' => write!(f, "\\r")?,
+            '\t' => write!(f, "\\t")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{}", c)?,
+        }
+    }
+    write!(f, "\"")
+}
+
+/// Parses a complete JSON document.
+pub fn parse(s: &str) -> anyhow::Result<Value> {
+    let mut parser = Parser {
+        chars: s.char_indices().peekable(),
+    };
+
+    let value = parser.value()?;
+    parser.skip_whitespace();
+    if let Some((i, c)) = parser.chars.next() {
+        bail!("Unexpected trailing character {:?} at offset {}.", c, i);
+    }
+
+    Ok(value)
+}
+
+struct Parser<'a> {
+    chars: Peekable<CharIndices<'a>>,
+}
+
+impl Parser<'_> {
+    fn skip_whitespace(&mut self) {
+        while matches!(self.chars.peek(), Some((_, ' ' | '\t' | '\n' | '\r'))) {
+            self.chars.next();
+        }
+    }
+
+    fn next(&mut self) -> anyhow::Result<(usize, char)> {
+        self.chars
+            .next()
+            .ok_or_else(|| anyhow!("Unexpected end of the JSON document."))
+    }
+
+    fn expect(&mut self, expected: char) -> anyhow::Result<()> {
+        match self.next()? {
+            (_, c) if c == expected => Ok(()),
+            (i, c) => bail!("Expected {:?} but found {:?} at offset {}.", expected, c, i),
+        }
+    }
+
+    fn expect_word(&mut self, word: &str, value: Value) -> anyhow::Result<Value> {
+        for c in word.chars() {
+            self.expect(c)?;
+        }
+        Ok(value)
+    }
+
+    fn value(&mut self) -> anyhow::Result<Value> {
+        self.skip_whitespace();
+        match self.chars.peek().copied() {
+            Some((_, '{')) => self.object(),
+            Some((_, '[')) => self.array(),
+            Some((_, '"')) => self.string().map(Value::String),
+            Some((_, 't')) => self.expect_word("true", Value::Bool(true)),
+            Some((_, 'f')) => self.expect_word("false", Value::Bool(false)),
+            Some((_, 'n')) => self.expect_word("null", Value::Null),
+            Some((_, '-' | '0'..='9')) => self.number(),
+            Some((i, c)) => bail!("Unexpected character {:?} at offset {}.", c, i),
+            None => bail!("Unexpected end of the JSON document."),
+        }
+    }
+
+    fn object(&mut self) -> anyhow::Result<Value> {
+        self.expect('{')?;
+        let mut fields = BTreeMap::new();
+
+        self.skip_whitespace();
+        if matches!(self.chars.peek(), Some((_, '}'))) {
+            self.chars.next();
+            return Ok(Value::Object(fields));
+        }
+
+        loop {
+            self.skip_whitespace();
+            let key = self.string()?;
+            self.skip_whitespace();
+            self.expect(':')?;
+            fields.insert(key, self.value()?);
+
+            self.skip_whitespace();
+            match self.next()? {
+                (_, ',') => continue,
+                (_, '}') => return Ok(Value::Object(fields)),
+                (i, c) => bail!("Expected ',' or '}}' but found {:?} at offset {}.", c, i),
+            }
+        }
+    }
+
+    fn array(&mut self) -> anyhow::Result<Value> {
+        self.expect('[')?;
+        let mut values = Vec::new();
+
+        self.skip_whitespace();
+        if matches!(self.chars.peek(), Some((_, ']'))) {
+            self.chars.next();
+            return Ok(Value::Array(values));
+        }
+
+        loop {
+            values.push(self.value()?);
+
+            self.skip_whitespace();
+            match self.next()? {
+                (_, ',') => continue,
+                (_, ']') => return Ok(Value::Array(values)),
+                (i, c) => bail!("Expected ',' or ']' but found {:?} at offset {}.", c, i),
+            }
+        }
+    }
+
+    fn string(&mut self) -> anyhow::Result<String> {
+        self.expect('"')?;
+        let mut s = String::new();
+
+        loop {
+            match self.next()? {
+                (_, '"') => return Ok(s),
+                (i, '\\') => {
+                    match self.next()? {
+                        (_, '"') => s.push('"'),
+                        (_, '\\') => s.push('\\'),
+                        (_, '/') => s.push('/'),
+                        (_, 'b') => s.push('\u{8}'),
+                        (_, 'f') => s.push('\u{c}'),
+                        (_, 'n') => s.push('\n'),
+                        (_, 'r') => s.push('\r'),
+                        (_, 't') => s.push('\t'),
+                        (_, 'u') => {
+                            let high = self.hex_escape()?;
+                            let code = if (0xD800..0xDC00).contains(&high) {
+                                // a surrogate pair encodes characters outside the basic plane
+                                let low = match (self.next()?, self.next()?) {
+                                    ((_, '\\'), (_, 'u')) => self.hex_escape()?,
+                                    _ => bail!("Unpaired surrogate escape at offset {}.", i),
+                                };
+                                if !(0xDC00..0xE000).contains(&low) {
+                                    bail!("Unpaired surrogate escape at offset {}.", i);
+                                }
+                                0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+                            } else {
+                                high
+                            };
+                            s.push(char::from_u32(code).ok_or_else(|| {
+                                anyhow!("Invalid unicode escape at offset {}.", i)
+                            })?);
+                        }
+                        (j, c) => bail!("Invalid escape {:?} at offset {}.", c, j),
+                    }
+                }
+                (i, c) if (c as u32) < 0x20 => {
+                    bail!("Unescaped control character {:?} at offset {}.", c, i)
+                }
+                (_, c) => s.push(c),
+            }
+        }
+    }
+
+    fn hex_escape(&mut self) -> anyhow::Result<u32> {
+        let mut code = 0;
+        for _ in 0..4 {
+            let (i, c) = self.next()?;
+            let digit = c
+                .to_digit(16)
+                .ok_or_else(|| anyhow!("Invalid hex digit {:?} at offset {}.", c, i))?;
+            code = code * 16 + digit;
+        }
+        Ok(code)
+    }
+
+    fn number(&mut self) -> anyhow::Result<Value> {
+        let mut text = String::new();
+        let start = self.chars.peek().map_or(0, |&(i, _)| i);
+        while let Some(&(_, c)) = self.chars.peek() {
+            if !matches!(c, '-' | '+' | '.' | 'e' | 'E' | '0'..='9') {
+                break;
+            }
+            text.push(c);
+            self.chars.next();
+        }
+
+        text.parse()
+            .map(Value::Number)
+            .map_err(|_| anyhow!("Invalid number {:?} at offset {}.", text, start))
+    }
+}
@@ -0,0 +1,45 @@
+//! A small maze generation library.
+//!
+//! ```no_run
+//! use labyrinth::Maze;
+//! use rand::{rngs::SmallRng, SeedableRng};
+//!
+//! let mut maze = Maze::new(51, 51);
+//! maze.populate(&mut SmallRng::seed_from_u64(42));
+//! # #[cfg(feature = "render")]
+//! maze.save_to_file("maze.png")?;
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![forbid(unsafe_code)]
+
+extern crate alloc;
+
+#[cfg(feature = "std")]
+mod gif;
+#[cfg(feature = "std")]
+mod hex;
+#[cfg(feature = "std")]
+mod jpeg;
+#[cfg(feature = "std")]
+mod json;
+mod maze;
+#[cfg(feature = "std")]
+mod maze3d;
+#[cfg(feature = "std")]
+mod polar;
+mod union_find;
+
+#[cfg(feature = "std")]
+pub use hex::{HexDirection, HexMaze};
+#[cfg(feature = "std")]
+pub use maze::{parse_hex_colour, Frames};
+pub use maze::{
+    seed_from_str, Algorithm, ColorScheme, Coord, Direction, GenStep, GrowingTreeSelection, Maze,
+    MazeBuilder, MazeGenerator, MazeStats, Symmetry, TileState, ValidationError,
+};
+#[cfg(feature = "std")]
+pub use maze3d::Maze3D;
+#[cfg(feature = "std")]
+pub use polar::{PolarDirection, PolarMaze};
@@ -1,10 +1,20 @@
-use std::path::PathBuf;
+#![forbid(unsafe_code)]
 
-use rand::{rngs::SmallRng, SeedableRng};
+use std::{
+    convert::TryFrom,
+    ffi::OsString,
+    fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use anyhow::Context;
 use structopt::StructOpt;
 
-mod maze;
-use maze::Maze;
+use labyrinth::{
+    parse_hex_colour, seed_from_str, Algorithm, ColorScheme, Maze, MazeBuilder, Symmetry,
+};
+use rgb::RGB8;
 
 #[derive(Debug, StructOpt)]
 #[structopt(
@@ -14,35 +24,611 @@ use maze::Maze;
     author = "Sam L. (@_tritoke)"
 )]
 struct Opt {
+    /// read options from a file of `key = value` lines, like a flat TOML file, where the keys
+    /// are the long names of the options. Anything given on the command line wins
+    #[structopt(long, parse(from_os_str))]
+    config: Option<PathBuf>,
+
     /// file to save the rendered image to
     #[structopt(short, long = "out", parse(from_os_str), default_value = "maze.png")]
     outfile: PathBuf,
 
-    /// seed for the RNG
+    /// check the maze in this file (png, txt, json or csv) has one start and one end with a way
+    /// between them instead of generating one, exiting with an error if it doesn't
+    #[structopt(long, parse(from_os_str))]
+    check: Option<PathBuf>,
+
+    /// seed for the RNG, a random one is picked and printed if not given
     #[structopt(short, long)]
     seed: Option<u64>,
 
-    /// width of the rendered image in pixels
+    /// seed the RNG from a word or phrase instead, it always gives the same maze
+    #[structopt(long, conflicts_with = "seed")]
+    seed_string: Option<String>,
+
+    /// number of mazes to generate, more than one numbers the files and uses seed, seed + 1, ...
+    #[structopt(short, long, default_value = "1")]
+    count: usize,
+
+    /// don't print the randomly picked seed
+    #[structopt(short, long)]
+    quiet: bool,
+
+    /// width of the maze in tiles, the image is this many times --scale pixels wide
     #[structopt(short, long, default_value = "500")]
     width: u32,
 
-    /// width of the rendered image in pixels
+    /// height of the maze in tiles, the image is this many times --scale pixels high
     #[structopt(short, long, default_value = "500")]
     height: u32,
+
+    /// algorithm used to generate the maze
+    #[structopt(short, long, default_value = "recursive-backtracker")]
+    algorithm: Algorithm,
+
+    /// draw the solution onto the rendered PNG, or as a line on an SVG
+    #[structopt(long)]
+    solve: bool,
+
+    /// fraction of the dead ends to knock through, adding loops to the maze
+    #[structopt(long, default_value = "0")]
+    braid: f64,
+
+    /// also save an animated GIF of the maze being generated to this file
+    #[structopt(long, parse(from_os_str))]
+    animate: Option<PathBuf>,
+
+    /// only keep every Nth step of the generation in the animation, by default enough are
+    /// skipped to keep it to about 1000 frames
+    #[structopt(long)]
+    frame_skip: Option<usize>,
+
+    /// join the edges of the maze up with the opposite edges, so passages can wrap around
+    #[structopt(long)]
+    wrap: bool,
+
+    /// generate the maze with mirrored walls: horizontal (left to right), vertical (top to
+    /// bottom) or rotational (the same after half a turn)
+    #[structopt(long)]
+    symmetry: Option<Symmetry>,
+
+    /// tile to start carving from as X,Y, a random one by default. Only works with the
+    /// recursive-backtracker algorithm
+    #[structopt(long, parse(try_from_str = parse_cell), conflicts_with = "symmetry")]
+    start_cell: Option<(usize, usize)>,
+
+    /// put the start and end at the two ends of the longest path through the maze
+    #[structopt(long)]
+    hardest: bool,
+
+    /// put the start on the top or left edge and the end on the bottom or right edge,
+    /// opening up the outer wall so the maze is entered from outside
+    #[structopt(long, conflicts_with = "hardest")]
+    border_entrances: bool,
+
+    /// keep the outermost ring of tiles as solid wall, generating the maze inside of it
+    #[structopt(long, conflicts_with_all = &["wrap", "border-entrances"])]
+    solid_border: bool,
+
+    /// colour the maze by the distance from the start instead
+    #[structopt(long, conflicts_with = "solve")]
+    heatmap: bool,
+
+    /// output format: file (picked from the --out extension), or ascii / box / term printed
+    /// to stdout, term draws in colour and falls back to ascii when piped
+    #[structopt(short, long, default_value = "file")]
+    format: Format,
+
+    /// colour the dead ends orange, for checking what --braid leaves behind
+    #[structopt(long, conflicts_with_all = &["solve", "heatmap"])]
+    highlight_dead_ends: bool,
+
+    /// with --format ascii, draw every tile two characters wide so the maze looks square
+    #[structopt(long, conflicts_with = "solve")]
+    pretty: bool,
+
+    /// size in pixels of each tile of the maze when saving a PNG, 1 by default. An SVG is
+    /// scaled up from 10 units a tile
+    // no default_value as clap counts a default as the flag being given when checking conflicts
+    #[structopt(long, conflicts_with_all = &["solve", "heatmap", "highlight-dead-ends"])]
+    scale: Option<u32>,
+
+    /// colour of the walls as #RRGGBB, black by default
+    #[structopt(
+        long,
+        parse(try_from_str = parse_hex_colour),
+        conflicts_with_all = &["solve", "heatmap", "highlight-dead-ends"]
+    )]
+    wall_color: Option<RGB8>,
+
+    /// colour of the passages as #RRGGBB, white by default
+    #[structopt(
+        long,
+        parse(try_from_str = parse_hex_colour),
+        conflicts_with_all = &["solve", "heatmap", "highlight-dead-ends"]
+    )]
+    path_color: Option<RGB8>,
+
+    /// colour of the start as #RRGGBB, green by default
+    #[structopt(
+        long,
+        parse(try_from_str = parse_hex_colour),
+        conflicts_with_all = &["solve", "heatmap", "highlight-dead-ends"]
+    )]
+    start_color: Option<RGB8>,
+
+    /// colour of the end as #RRGGBB, red by default
+    #[structopt(
+        long,
+        parse(try_from_str = parse_hex_colour),
+        conflicts_with_all = &["solve", "heatmap", "highlight-dead-ends"]
+    )]
+    end_color: Option<RGB8>,
+
+    /// swap the wall and passage colours, giving white walls on black by default
+    #[structopt(long, conflicts_with_all = &["solve", "heatmap", "highlight-dead-ends"])]
+    invert: bool,
+
+    /// save the negative of the maze, with the walls as passages and the passages as walls
+    #[structopt(long, conflicts_with = "solve")]
+    complement: bool,
+
+    /// pixels of passage coloured border to put around the maze when saving a PNG
+    #[structopt(
+        long,
+        conflicts_with_all = &["solve", "heatmap", "highlight-dead-ends", "transparent"]
+    )]
+    margin: Option<u32>,
+
+    /// leave the passages transparent when saving a PNG
+    #[structopt(long, conflicts_with_all = &["solve", "heatmap", "highlight-dead-ends"])]
+    transparent: bool,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Format {
+    File,
+    Ascii,
+    Box,
+    Term,
+}
+
+impl FromStr for Format {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "file" => Ok(Format::File),
+            "ascii" => Ok(Format::Ascii),
+            "box" => Ok(Format::Box),
+            "term" => Ok(Format::Term),
+            _ => Err(anyhow::anyhow!(
+                "Unknown format {:?}, expected one of: file, ascii, box, term.",
+                s
+            )),
+        }
+    }
+}
+
+// a tile given on the command line as X,Y
+fn parse_cell(s: &str) -> anyhow::Result<(usize, usize)> {
+    let (x, y) = s
+        .split_once(',')
+        .with_context(|| format!("Invalid tile {:?}, expected X,Y like 0,0.", s))?;
+    let coordinate = |n: &str| {
+        n.trim()
+            .parse()
+            .with_context(|| format!("Invalid tile {:?}, expected X,Y like 0,0.", s))
+    };
+
+    Ok((coordinate(x)?, coordinate(y)?))
+}
+
+// the options from the command line, filled in from the --config file if there is one
+fn options() -> anyhow::Result<Opt> {
+    let args: Vec<OsString> = std::env::args_os().collect();
+    let opt = Opt::from_clap(&Opt::clap().get_matches_from(&args));
+    let Some(path) = &opt.config else {
+        return Ok(opt);
+    };
+
+    let config = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read the config file {}.", path.display()))?;
+    Ok(merge_config(args, parse_config(&config, path)?, path))
+}
+
+// the options from the command line `args`, with the options in the config file at `path`
+// filling in the ones which weren't given
+fn merge_config(args: Vec<OsString>, pairs: Vec<(String, String)>, path: &Path) -> Opt {
+    let matches = Opt::clap().get_matches_from(&args);
+    let is_given = |config: &structopt::clap::ArgMatches, name: &str| {
+        config.occurrences_of(name) > 0 && matches.occurrences_of(name) > 0
+    };
+
+    let program = args.first().cloned().unwrap_or_else(|| "labyrinth".into());
+    let mut merged = vec![program.clone()];
+    for (key, value) in pairs {
+        let mut given = vec![program.clone(), format!("--{}", key).into()];
+        match value.as_str() {
+            "true" => {}
+            // a flag can't be turned back off once it is given, so false just leaves it out
+            "false" => continue,
+            _ => given.push(value.into()),
+        }
+
+        // one option at a time shows whether the key exists and which option it sets, without
+        // any of the conflicts between options getting in the way
+        match Opt::clap().get_matches_from_safe(&given) {
+            Err(e) if e.kind == structopt::clap::ErrorKind::UnknownArgument => {
+                eprintln!(
+                    "Ignoring {:?} in {}, it isn't an option.",
+                    key,
+                    path.display()
+                );
+                continue;
+            }
+            // the defaults are in the matches too, but never as an occurrence
+            Ok(config) if config.args.keys().any(|&name| is_given(&config, name)) => {
+                continue;
+            }
+            // a bad value is reported when everything is parsed together below
+            _ => {}
+        }
+        merged.extend(given.into_iter().skip(1));
+    }
+    merged.extend(args.into_iter().skip(1));
+
+    Opt::from_clap(&Opt::clap().get_matches_from(merged))
+}
+
+// the (key, value) pairs from the text of the config file at `path`, one `key = value` per line
+// with keys written in either snake_case or kebab-case. blank lines and lines starting with #
+// are skipped, and values can be quoted
+fn parse_config(config: &str, path: &Path) -> anyhow::Result<Vec<(String, String)>> {
+    let mut pairs = Vec::new();
+    for (number, line) in config.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let (key, value) = line.split_once('=').with_context(|| {
+            format!(
+                "Invalid line {} of the config file {}, expected key = value.",
+                number + 1,
+                path.display()
+            )
+        })?;
+        let value = value.trim();
+        let value = value
+            .strip_prefix('"')
+            .and_then(|value| value.strip_suffix('"'))
+            .unwrap_or(value);
+        pairs.push((key.trim().replace('_', "-"), value.to_string()));
+    }
+
+    Ok(pairs)
+}
+
+// how long each frame of an --animate GIF is shown for
+const ANIMATION_FRAME_DELAY_MS: u16 = 20;
+
+// about how many frames an animation has when --frame-skip isn't given
+const ANIMATION_FRAMES: u64 = 1000;
+
+// carving changes about one tile in every two, so this many steps to a frame keeps the
+// animation of a maze this size to around ANIMATION_FRAMES frames
+fn default_frame_skip(width: u32, height: u32) -> usize {
+    let steps = u64::from(width) * u64::from(height) / 2;
+    usize::try_from(steps / ANIMATION_FRAMES).map_or(usize::MAX, |skip| skip.max(1))
 }
 
 fn main() -> anyhow::Result<()> {
-    let opt = Opt::from_args();
+    let opt = options()?;
+    if let Some(path) = &opt.check {
+        return check(path);
+    }
 
-    let mut rng = if let Some(seed) = opt.seed {
-        SmallRng::seed_from_u64(seed)
-    } else {
-        SmallRng::from_entropy()
+    let default = ColorScheme::default();
+    let scale = opt.scale.unwrap_or(1);
+    let margin = opt.margin.unwrap_or(0);
+    let scheme = colour_scheme(&opt);
+
+    if opt.count == 0 {
+        anyhow::bail!("Cannot generate 0 mazes, --count must be at least 1.");
+    }
+    if opt.format == Format::File {
+        check_output(&opt, &scheme)?;
+    }
+
+    // pick the seed up front so an interesting maze can always be generated again
+    let given = opt
+        .seed
+        .or_else(|| opt.seed_string.as_deref().map(seed_from_str));
+    let seed = given.unwrap_or_else(rand::random);
+    if given.is_none() && !opt.quiet {
+        eprintln!(
+            "Using seed {}, pass --seed {} to generate this maze again.",
+            seed, seed
+        );
+    }
+
+    for index in 0..opt.count {
+        let mut builder = MazeBuilder::new()
+            .seed(seed.wrapping_add(index as u64))
+            .width(opt.width)
+            .height(opt.height)
+            .algorithm(opt.algorithm)
+            .braid(opt.braid)
+            .hardest(opt.hardest)
+            .wrap(opt.wrap)
+            .border_entrances(opt.border_entrances)
+            .solid_border(opt.solid_border);
+        if let Some(symmetry) = opt.symmetry {
+            builder = builder.symmetry(symmetry);
+        }
+        if let Some(start) = opt.start_cell {
+            builder = builder.start_cell(start);
+        }
+
+        let maze = if let Some(animation) = &opt.animate {
+            let frame_skip = opt
+                .frame_skip
+                .unwrap_or_else(|| default_frame_skip(opt.width, opt.height));
+            let (maze, frames) = builder.try_build_frames(frame_skip)?;
+            let animation = numbered_path(animation, index, opt.count);
+            frames.save_gif(&animation, ANIMATION_FRAME_DELAY_MS)?;
+            maze
+        } else {
+            build(&builder, opt.quiet)?
+        };
+        let maze = if opt.complement {
+            maze.complement()
+        } else {
+            maze
+        };
+
+        let outfile = numbered_path(&opt.outfile, index, opt.count);
+        if opt.format == Format::Ascii && opt.solve {
+            println!("{}", maze.to_ascii_with_solution());
+        } else if opt.format == Format::Ascii && opt.pretty {
+            println!("{}", maze.to_ascii_wide());
+        } else if opt.format == Format::Ascii {
+            println!("{}", maze.to_ascii());
+        } else if opt.format == Format::Box && opt.solve {
+            println!("{}", maze.to_box_drawing_with_solution());
+        } else if opt.format == Format::Box {
+            println!("{}", maze.to_box_drawing());
+        } else if opt.format == Format::Term {
+            maze.print_colored();
+        } else if opt.solve && is_svg(&outfile) {
+            maze.save_svg_solution(&outfile, "#0000ff")?;
+        } else if opt.solve {
+            maze.save_solution_to_file(&outfile)?;
+        } else if opt.heatmap {
+            maze.save_distance_heatmap(&outfile)?;
+        } else if opt.highlight_dead_ends {
+            maze.save_dead_ends(&outfile)?;
+        } else if opt.transparent {
+            maze.save_to_file_transparent(&outfile, &scheme, scale)?;
+        } else if margin != 0 {
+            maze.save_to_file_with_margin(&outfile, &scheme, scale, margin)?;
+        } else if scale != 1 && is_svg(&outfile) {
+            maze.save_svg_scaled(&outfile, scale)?;
+        } else if scale != 1 || scheme != default {
+            maze.save_to_file_with_scheme(&outfile, &scheme, scale)?;
+        } else {
+            maze.save(&outfile)?;
+        }
+    }
+
+    Ok(())
+}
+
+// the default colours with any given on the command line swapped in
+fn colour_scheme(opt: &Opt) -> ColorScheme {
+    let default = ColorScheme::default();
+    let scheme = ColorScheme {
+        wall: opt.wall_color.unwrap_or(default.wall),
+        empty: opt.path_color.unwrap_or(default.empty),
+        start: opt.start_color.unwrap_or(default.start),
+        end: opt.end_color.unwrap_or(default.end),
+        crossing: default.crossing,
     };
+    if opt.invert {
+        scheme.inverted()
+    } else {
+        scheme
+    }
+}
+
+// only `Maze::save` picks the format from the extension, the options which change how the maze
+// is drawn only have PNG and sometimes SVG writers, so they are refused for any other file
+fn check_output(opt: &Opt, scheme: &ColorScheme) -> anyhow::Result<()> {
+    let is_png = opt
+        .outfile
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("png"));
+    let png = (is_png, "a PNG");
+    let png_or_svg = (is_png || is_svg(&opt.outfile), "a PNG or an SVG");
+
+    let drawn = [
+        (opt.solve, "--solve", png_or_svg),
+        (opt.scale.is_some(), "--scale", png_or_svg),
+        (opt.heatmap, "--heatmap", png),
+        (opt.highlight_dead_ends, "--highlight-dead-ends", png),
+        (opt.transparent, "--transparent", png),
+        (opt.margin.is_some(), "--margin", png),
+        (*scheme != ColorScheme::default(), "the colour options", png),
+    ];
+    match drawn
+        .iter()
+        .find(|&&(used, _, (supported, _))| used && !supported)
+    {
+        Some((_, option, (_, formats))) => anyhow::bail!(
+            "Cannot save {} with {}, it can only be saved as {}.",
+            opt.outfile.display(),
+            option,
+            formats
+        ),
+        None => Ok(()),
+    }
+}
+
+// the solution is drawn as a line for SVGs rather than tinting the tiles it passes through
+fn is_svg(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("svg"))
+}
 
-    let mut maze = Maze::new(opt.width, opt.height);
-    maze.populate(&mut rng);
-    maze.save_to_file(&opt.outfile)?;
+// loads a maze from a file and says whether it is valid, the error explains what is wrong
+fn check(path: &Path) -> anyhow::Result<()> {
+    let maze = Maze::load(path)?;
+    maze.validate()
+        .with_context(|| format!("{} is not a valid maze.", path.display()))?;
 
+    println!("{} is a valid maze.", path.display());
     Ok(())
 }
+
+// generates the maze, drawing a percentage on stderr as it goes when it is a terminal
+#[cfg(feature = "progress")]
+fn build(builder: &MazeBuilder, quiet: bool) -> anyhow::Result<Maze> {
+    use std::io::{self, IsTerminal};
+
+    if quiet || !io::stderr().is_terminal() {
+        return builder.try_build();
+    }
+
+    builder.try_build_with_progress(|finished, total| {
+        eprint!("\rGenerating the maze: {:>3}%", finished * 100 / total);
+        if finished == total {
+            eprintln!();
+        }
+    })
+}
+
+#[cfg(not(feature = "progress"))]
+fn build(builder: &MazeBuilder, _quiet: bool) -> anyhow::Result<Maze> {
+    builder.try_build()
+}
+
+// the file for the maze at `index` out of `count`, a single maze keeps the path as it is
+// otherwise the index is added to the stem, zero padded to at least three digits so the files
+// sort in order, so maze.png becomes maze_000.png, maze_001.png, ...
+fn numbered_path(path: &Path, index: usize, count: usize) -> PathBuf {
+    if count <= 1 {
+        return path.to_path_buf();
+    }
+
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let width = (count - 1).to_string().len().max(3);
+    let mut name = format!("{}_{:0width$}", stem, index, width = width);
+    if let Some(extension) = path.extension() {
+        name.push('.');
+        name.push_str(&extension.to_string_lossy());
+    }
+
+    path.with_file_name(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<OsString> {
+        args.iter().map(OsString::from).collect()
+    }
+
+    #[test]
+    fn config_fills_in_what_the_command_line_leaves_out() {
+        let path = Path::new("maze.toml");
+        let config = "# a small maze\nwidth = 41\nheight = \"21\"\nsolid_border = true\n";
+        let pairs = parse_config(config, path).unwrap();
+
+        let opt = merge_config(
+            args(&["labyrinth", "--config", "maze.toml"]),
+            pairs.clone(),
+            path,
+        );
+        assert_eq!((opt.width, opt.height), (41, 21));
+        assert!(opt.solid_border);
+
+        let cli = args(&["labyrinth", "--config", "maze.toml", "--height", "31"]);
+        let opt = merge_config(cli, pairs, path);
+        assert_eq!((opt.width, opt.height), (41, 31));
+    }
+
+    #[test]
+    fn config_lines_need_an_equals_sign() {
+        let err = parse_config("width = 41\nheight 21\n", Path::new("maze.toml")).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid line 2 of the config file maze.toml, expected key = value."
+        );
+    }
+
+    #[test]
+    fn width_and_height_count_tiles() {
+        let opt = Opt::from_iter(args(&["labyrinth", "-w", "31", "-h", "17", "--scale", "4"]));
+        assert_eq!((opt.width, opt.height, opt.scale), (31, 17, Some(4)));
+
+        let maze = MazeBuilder::new()
+            .width(opt.width)
+            .height(opt.height)
+            .build();
+        assert_eq!(maze.dimensions(), (31, 17));
+        assert_eq!(maze.scaled_dimensions(4), (124, 68));
+
+        let opt = Opt::from_iter(args(&["labyrinth"]));
+        assert_eq!((opt.width, opt.height, opt.scale), (500, 500, None));
+    }
+
+    #[test]
+    fn png_only_options_need_a_png() {
+        let check = |cli: &[&str]| {
+            let opt = Opt::from_iter(args(cli));
+            check_output(&opt, &colour_scheme(&opt))
+        };
+
+        let err = check(&["labyrinth", "-o", "out.jpg", "--scale", "3"]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Cannot save out.jpg with --scale, it can only be saved as a PNG or an SVG."
+        );
+        assert!(check(&["labyrinth", "-o", "out.svg", "--scale", "3"]).is_ok());
+        assert!(check(&["labyrinth", "-o", "out.svg", "--scale", "3", "--invert"]).is_err());
+        assert!(check(&["labyrinth", "-o", "out.bogus", "--scale", "2"]).is_err());
+        assert!(check(&["labyrinth", "-o", "out.json", "--invert"]).is_err());
+        let err = check(&["labyrinth", "-o", "out.svg", "--wall-color", "#202060"]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Cannot save out.svg with the colour options, it can only be saved as a PNG."
+        );
+        assert!(check(&["labyrinth", "-o", "out.jpg", "--transparent"]).is_err());
+        assert!(check(&["labyrinth", "-o", "x.svg", "--transparent"]).is_err());
+        assert!(check(&["labyrinth", "-o", "out.svg", "--heatmap"]).is_err());
+
+        assert!(check(&["labyrinth", "-o", "out.PNG", "--scale", "3", "--invert"]).is_ok());
+        assert!(check(&["labyrinth", "-o", "out.svg", "--solve"]).is_ok());
+        assert!(check(&["labyrinth", "-o", "out.png", "--solve"]).is_ok());
+        assert!(check(&["labyrinth", "-s", "1", "--solve", "-o", "x.txt"]).is_err());
+        assert!(check(&["labyrinth", "-o", "out.txt"]).is_ok());
+    }
+
+    #[test]
+    fn numbered_paths_pad_the_index() {
+        let path = Path::new("out/maze.png");
+        assert_eq!(numbered_path(path, 0, 1), path);
+        assert_eq!(numbered_path(path, 0, 2), Path::new("out/maze_000.png"));
+        assert_eq!(numbered_path(path, 1, 2), Path::new("out/maze_001.png"));
+        assert_eq!(
+            numbered_path(path, 1234, 5000),
+            Path::new("out/maze_1234.png")
+        );
+        assert_eq!(
+            numbered_path(Path::new("maze"), 7, 10),
+            Path::new("maze_007")
+        );
+    }
+}
@@ -1,204 +1,1425 @@
-use std::{ffi::OsStr, fs::File, io::BufWriter, path::Path};
+use alloc::{vec, vec::Vec};
+use core::convert::TryFrom;
+#[cfg(feature = "std")]
+use std::{
+    ffi::OsStr,
+    fs::{self, File},
+    io::BufWriter,
+    path::Path,
+    str::FromStr,
+};
 
+#[cfg(feature = "std")]
 use anyhow::{self, Context};
+#[cfg(feature = "std")]
 use grid::Grid;
-use rand::{prelude::SliceRandom, Rng};
-use rgb::{ComponentBytes, RGB8};
+use rand::Rng;
+use rgb::{RGB8, RGBA8};
 
-#[derive(Debug, Clone, Eq, PartialEq)]
+mod aldous_broder;
+mod animate;
+#[cfg(feature = "parallel")]
+mod batch;
+mod binary_tree;
+mod braid;
+mod builder;
+mod cellular;
+mod complement;
+mod coord;
+#[cfg(feature = "std")]
+mod csv;
+#[cfg(feature = "std")]
+mod diff;
+#[cfg(feature = "std")]
+mod dot;
+mod eller;
+mod entrances;
+mod generator;
+mod growing_tree;
+mod hunt_and_kill;
+#[cfg(feature = "render")]
+mod image;
+#[cfg(feature = "std")]
+mod jpeg;
+#[cfg(feature = "std")]
+mod json;
+mod kruskal;
+mod origin_shift;
+mod prim;
+mod recursive_division;
+mod rng;
+mod rooms;
+mod solve;
+mod sparsify;
+mod stats;
+#[cfg(feature = "std")]
+mod svg;
+mod symmetry;
+mod text;
+mod thin_walls;
+mod tiles;
+mod transform;
+mod validate;
+mod weave;
+mod wilson;
+
+#[cfg(feature = "std")]
+pub use animate::Frames;
+pub use builder::{seed_from_str, MazeBuilder};
+use coord::widen;
+pub use coord::Coord;
+pub use generator::{GenStep, MazeGenerator};
+pub use growing_tree::GrowingTreeSelection;
+#[cfg(feature = "render")]
+pub(crate) use image::encode_png;
+pub(crate) use rng::{gen_coord, gen_index};
+pub use stats::MazeStats;
+#[cfg(feature = "std")]
+pub(crate) use svg::hex_colour;
+pub use symmetry::Symmetry;
+use tiles::Tiles;
+pub use validate::ValidationError;
+
+/// A maze made up of a grid of tiles, where each tile is either a wall or a passage.
+///
+/// Two mazes are equal if they have the same tiles and both wrap or don't, regardless of how
+/// they were generated.
+#[derive(Debug, Clone)]
 pub struct Maze {
     width: u32,
     height: u32,
-    data: Grid<TileState>,
-    visited: Grid<bool>,
+    data: Tiles<TileState>,
+    visited: Tiles<bool>,
+    // tiles which are false are outside of the maze's shape and can never be carved
+    mask: Tiles<bool>,
+    // only set while populate_recording is snapshotting the maze
+    recording: Option<animate::Recording>,
+    // whether the edges join up with the opposite edges, making the maze a torus
+    wrap: bool,
+    // built by the first origin_shift_step, set_tile throws it away whenever a tile changes so
+    // the next step builds it again from the new tiles. origin_shift_step takes it out while
+    // it carves, so its own changes keep it
+    origin_shift: Option<origin_shift::OriginShift>,
+    // where the Start and End tiles are, kept in step with the tiles by set_tile
+    start: Option<Coord>,
+    end: Option<Coord>,
+    // the settings of the builder which made the maze, with the seed filled in. set_tile clears
+    // it as soon as a tile changes, as the settings wouldn't make the same maze any more
+    generated_by: Option<MazeBuilder>,
+}
+
+impl PartialEq for Maze {
+    fn eq(&self, other: &Self) -> bool {
+        // visited is only bookkeeping for the generators so it has no say
+        self.width == other.width
+            && self.height == other.height
+            && self.data == other.data
+            && self.wrap == other.wrap
+    }
 }
 
+impl Eq for Maze {}
+
 impl Maze {
+    /// Creates a maze of solid wall, `width` x `height` tiles in size.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either the width or height is zero, see `try_new` for a fallible version.
     pub fn new(width: u32, height: u32) -> Self {
         Self {
             width,
             height,
-            data: Grid::new(width as usize, height as usize),
-            visited: Grid::init(width as usize, height as usize, false),
+            data: Tiles::new(width, height),
+            visited: Tiles::init(width, height, false),
+            mask: Tiles::init(width, height, true),
+            recording: None,
+            wrap: false,
+            origin_shift: None,
+            start: None,
+            end: None,
+            generated_by: None,
+        }
+    }
+
+    /// Creates a maze of solid wall confined to a shape, tiles which are `false` in the mask are
+    /// off limits and stay as wall forever. The mask is indexed by `(x, y)` like the maze.
+    ///
+    /// `populate` only carves the tiles inside the mask, the other generators never carve into
+    /// the masked tiles but can leave parts of the shape unreachable.
+    ///
+    /// ```
+    /// use grid::Grid;
+    /// use labyrinth::{Maze, TileState};
+    /// use rand::{rngs::SmallRng, SeedableRng};
+    ///
+    /// // block off the top right corner of a maze wider than it is tall
+    /// let mut mask = Grid::init(9, 5, true);
+    /// *mask.get_mut(8, 0).unwrap() = false;
+    ///
+    /// let mut maze = Maze::with_mask(9, 5, mask)?;
+    /// maze.populate(&mut SmallRng::seed_from_u64(1));
+    /// assert_eq!(maze.tile(8, 0), Some(TileState::Wall));
+    /// assert!(maze.solution().is_some());
+    ///
+    /// // and a bigger hole in the middle is never carved into, whatever the seed
+    /// let mut mask = Grid::init(15, 11, true);
+    /// for (x, y) in (5..10).flat_map(|x| (3..8).map(move |y| (x, y))) {
+    ///     *mask.get_mut(x, y).unwrap() = false;
+    /// }
+    /// for seed in 0..8 {
+    ///     let mut maze = Maze::with_mask(15, 11, mask.clone())?;
+    ///     maze.populate(&mut SmallRng::seed_from_u64(seed));
+    ///     for (x, y) in (0..15).flat_map(|x| (0..11).map(move |y| (x, y))) {
+    ///         if !mask.get(x, y).unwrap() {
+    ///             assert_eq!(maze.tile(x, y), Some(TileState::Wall));
+    ///         }
+    ///     }
+    /// }
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn with_mask(width: u32, height: u32, mask: Grid<bool>) -> anyhow::Result<Self> {
+        if mask.size() != (widen(width), widen(height)) {
+            let (mask_width, mask_height) = mask.size();
+            anyhow::bail!(
+                "Cannot use a {}x{} mask for a {}x{} maze, they must be the same size.",
+                mask_width,
+                mask_height,
+                width,
+                height
+            );
+        }
+
+        // the sizes match, so the mask is no bigger than the maze
+        let maze = Self::try_new(width, height)?;
+        Ok(Self {
+            mask: Tiles::from_grid(&mask).unwrap_or_else(|| maze.blank_tiles()),
+            ..maze
+        })
+    }
+
+    /// Creates a maze of solid wall, returning an error if either the width or height is zero.
+    ///
+    /// ```
+    /// use labyrinth::Maze;
+    ///
+    /// assert!(Maze::try_new(0, 5).is_err());
+    /// assert!(Maze::try_new(5, 0).is_err());
+    /// assert!(Maze::try_new(1, 1).is_ok());
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn try_new(width: u32, height: u32) -> anyhow::Result<Self> {
+        if width == 0 || height == 0 {
+            anyhow::bail!(
+                "Cannot create a {}x{} maze, the width and height must both be at least 1.",
+                width,
+                height
+            );
+        }
+
+        Ok(Self::new(width, height))
+    }
+
+    // a finished maze built from existing tiles, every tile counts as visited so nothing gets
+    // carved
+    fn from_tiles(width: u32, height: u32, data: Tiles<TileState>) -> Self {
+        let find = |state| row_major(width, height).find(|&tile| data.at(tile) == Some(&state));
+        let (start, end) = (find(TileState::Start), find(TileState::End));
+        let (columns, rows) = data.dimensions();
+
+        Self {
+            width,
+            height,
+            data,
+            visited: Tiles::init(columns, rows, true),
+            mask: Tiles::init(columns, rows, true),
+            recording: None,
+            wrap: false,
+            origin_shift: None,
+            start,
+            end,
+            generated_by: None,
         }
     }
 
+    /// The width and height of the maze in tiles.
+    pub fn dimensions(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    /// Makes the maze wrap around, so the tiles along each edge are next to the tiles along the
+    /// opposite edge like on a torus. Set this before generating the maze.
+    ///
+    /// Only `populate` carves passages across the edges, the other generators treat the edges
+    /// as solid, but solving and everything else built on the neighbours of a tile still go
+    /// across them.
+    ///
+    #[cfg_attr(feature = "std", doc = "```")]
+    #[cfg_attr(not(feature = "std"), doc = "```ignore")]
+    /// use labyrinth::Maze;
+    ///
+    /// let mut maze = Maze::from_ascii("S# E\n####")?;
+    /// assert_eq!(maze.solve_bfs(), None);
+    /// // stepping west off the left edge comes back in on the right
+    /// maze.set_wrap(true);
+    /// assert_eq!(maze.solve_bfs(), Some(vec![(0, 0), (3, 0)]));
+    ///
+    /// // two tiles across, east and west of a tile are the same tile, which is one neighbour
+    /// let mut narrow = Maze::from_ascii("SE")?;
+    /// narrow.set_wrap(true);
+    /// assert_eq!(narrow.open_neighbours(0, 0).collect::<Vec<_>>(), vec![(1, 0)]);
+    /// assert!(narrow.is_perfect());
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn set_wrap(&mut self, wrap: bool) {
+        self.wrap = wrap;
+    }
+
+    /// Whether the edges of the maze join up with the opposite edges, see `set_wrap`.
+    pub fn wraps(&self) -> bool {
+        self.wrap
+    }
+
+    /// The start of the maze, `None` if it doesn't have one.
+    ///
+    /// ```
+    /// use labyrinth::MazeBuilder;
+    /// use rgb::RGB8;
+    ///
+    /// let maze = MazeBuilder::new().width(21).height(21).seed(2).build();
+    /// let (x, y) = maze.start().unwrap();
+    /// assert_eq!(maze.render_scaled(1)[y * 21 + x], RGB8::new(0x00, 0xFF, 0x00));
+    /// ```
+    pub fn start(&self) -> Option<(usize, usize)> {
+        self.start.map(Into::into)
+    }
+
+    /// The end of the maze, `None` if it doesn't have one.
+    pub fn end(&self) -> Option<(usize, usize)> {
+        self.end.map(Into::into)
+    }
+
+    /// Moves the start to the passage at `(x, y)`, the old start becomes a plain passage.
+    ///
+    /// Returns an error if the tile isn't an empty passage, so the start can't be put on a wall
+    /// or on top of the end.
+    #[cfg(feature = "std")]
+    pub fn set_start(&mut self, x: usize, y: usize) -> anyhow::Result<()> {
+        self.move_marker((x, y), TileState::Start)
+    }
+
+    /// Moves the end to the passage at `(x, y)`, see `set_start`.
+    #[cfg(feature = "std")]
+    pub fn set_end(&mut self, x: usize, y: usize) -> anyhow::Result<()> {
+        self.move_marker((x, y), TileState::End)
+    }
+
+    // moves the start or end to another tile, carving away the old one
+    #[cfg(feature = "std")]
+    fn move_marker(&mut self, (x, y): (usize, usize), marker: TileState) -> anyhow::Result<()> {
+        let (name, old) = match marker {
+            TileState::Start => ("start", self.start),
+            _ => ("end", self.end),
+        };
+        let tile = Coord::try_from((x, y))
+            .ok()
+            .filter(|&tile| self.data.at(tile) == Some(&TileState::Empty) || old == Some(tile))
+            .filter(|&tile| !self.is_masked(tile));
+        let Some(tile) = tile else {
+            anyhow::bail!(
+                "Cannot put the {} at ({}, {}), it has to go on an empty passage.",
+                name,
+                x,
+                y
+            );
+        };
+        if old == Some(tile) {
+            return Ok(());
+        }
+
+        if let Some(old) = old {
+            self.carve(old);
+        }
+        self.set_tile(tile, marker);
+        Ok(())
+    }
+
+    /// The tile at `(x, y)`, `None` if it is outside of the maze. `x` counts along a row from the
+    /// left and `y` counts down the rows from the top, the same order the tiles are rendered in.
+    ///
+    #[cfg_attr(feature = "std", doc = "```")]
+    #[cfg_attr(not(feature = "std"), doc = "```ignore")]
+    /// use labyrinth::{Maze, TileState};
+    ///
+    /// let maze = Maze::from_ascii("S ##\n#  E")?;
+    /// assert_eq!(maze.tile(0, 0), Some(TileState::Start));
+    /// assert_eq!(maze.tile(2, 0), Some(TileState::Wall));
+    /// assert_eq!(maze.tile(1, 1), Some(TileState::Empty));
+    /// assert_eq!(maze.tile(3, 1), Some(TileState::End));
+    /// assert_eq!(maze.tile(4, 0), None);
+    /// assert_eq!(maze.tile(0, 2), None);
+    ///
+    /// // the pixels are in row order, so (x, y) is pixel y * width + x
+    /// let luma = maze.render_luma();
+    /// assert_eq!(luma[1 * 4 + 1], maze.tile(1, 1).unwrap().luma());
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn tile(&self, x: usize, y: usize) -> Option<TileState> {
+        self.data.get(x, y).copied()
+    }
+
+    /// The passage tiles which can be stepped to from the passage at `(x, y)`, walls and the
+    /// outside of the maze are skipped. Walls have no open neighbours.
+    ///
+    #[cfg_attr(feature = "std", doc = "```")]
+    #[cfg_attr(not(feature = "std"), doc = "```ignore")]
+    /// use labyrinth::Maze;
+    ///
+    /// let open = Maze::from_ascii("   \n   \n   ")?;
+    /// assert_eq!(open.dimensions(), (3, 3));
+    /// let count = |x, y| open.open_neighbours(x, y).count();
+    /// // corners, edges and the middle
+    /// assert_eq!(count(0, 0), 2);
+    /// assert_eq!(count(2, 2), 2);
+    /// assert_eq!(count(1, 0), 3);
+    /// assert_eq!(count(0, 1), 3);
+    /// assert_eq!(count(1, 1), 4);
+    /// assert_eq!(count(3, 0), 0);
+    ///
+    /// let walled = Maze::from_ascii("S #\n# #\n  E")?;
+    /// assert_eq!(walled.open_neighbours(0, 0).collect::<Vec<_>>(), vec![(1, 0)]);
+    /// let mut edge: Vec<_> = walled.open_neighbours(1, 2).collect();
+    /// edge.sort();
+    /// assert_eq!(edge, vec![(0, 2), (1, 1), (2, 2)]);
+    /// assert_eq!(walled.open_neighbours(2, 0).count(), 0);
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn open_neighbours(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> {
+        let neighbours = match Coord::try_from((x, y)) {
+            Ok(tile) if self.is_passage(tile) => self.passage_neighbours(tile),
+            _ => Vec::new(),
+        };
+        neighbours.into_iter().map(Into::into)
+    }
+
+    pub fn populate_with<R: Rng + ?Sized>(&mut self, algorithm: Algorithm, rng: &mut R) {
+        match algorithm {
+            Algorithm::RecursiveBacktracker => self.populate(rng),
+            Algorithm::Prim => self.populate_prim(rng),
+            Algorithm::Kruskal => self.populate_kruskal(rng),
+            Algorithm::Wilson => self.populate_wilson(rng),
+            Algorithm::AldousBroder => self.populate_aldous_broder(rng),
+            Algorithm::RecursiveDivision => self.populate_recursive_division(rng),
+            Algorithm::Eller => self.populate_eller(rng),
+            Algorithm::BinaryTree => self.populate_binary_tree(rng),
+            Algorithm::Sidewinder => self.populate_sidewinder(rng),
+            Algorithm::HuntAndKill => self.populate_hunt_and_kill(rng),
+            Algorithm::Weave => self.populate_weave(rng),
+        }
+    }
+
+    /// Generates the maze with a randomized depth first search, see `MazeGenerator` to run it
+    /// a step at a time.
+    ///
+    /// Neighbours are found the same way along both axes, so long thin mazes come out just as
+    /// connected as square ones.
+    ///
+    /// ```
+    /// use labyrinth::Maze;
+    /// use rand::{rngs::SmallRng, SeedableRng};
+    ///
+    /// for &(width, height) in &[(200, 10), (10, 200), (1, 40), (40, 1)] {
+    ///     let mut maze = Maze::new(width, height);
+    ///     maze.populate(&mut SmallRng::seed_from_u64(1));
+    ///     assert!(maze.is_perfect());
+    ///     assert!(maze.solve_bfs().is_some());
+    /// }
+    /// ```
     pub fn populate<R: Rng + ?Sized>(&mut self, rng: &mut R) {
-        let start_x = rng.gen_range(0..self.width) as usize;
-        let start_y = rng.gen_range(0..self.height) as usize;
-
-        // now perform a randomized depth first search
-        let mut stack: Vec<(usize, usize)> = vec![(start_x, start_y)];
-        // cannot be out of range
-        unsafe { *self.visited.get_unchecked_mut(start_x, start_y) = true };
-
-        while let Some(&(x, y)) = stack.last() {
-            // shuffle the neighbours
-            let mut neighbours = vec![
-                (x, y + 1, Direction::North),
-                (x + 1, y, Direction::East),
-                (x, y.saturating_sub(1), Direction::South),
-                (x.saturating_sub(1), y, Direction::West),
-            ];
-            neighbours.shuffle(rng);
-
-            // write to the grid after we have found tiles with no neighbours
-            if self.data.get(x, y).is_some() {
-                // find a neighbour if one exists
-                if let Some((new_x, new_y, _)) = neighbours
-                    .iter()
-                    .copied()
-                    .find(|(x, y, d)| self.is_valid_neighbour(*x, *y, *d))
-                {
-                    let tile: &mut TileState = self.data.get_mut(x, y).unwrap();
-                    *tile = TileState::Empty;
-
-                    unsafe { *self.visited.get_unchecked_mut(new_x, new_y) = true };
-
-                    stack.push((new_x, new_y));
-                } else {
-                    let tile: &mut TileState = self.data.get_mut(x, y).unwrap();
-                    *tile = TileState::Empty;
-                    stack.truncate(stack.len() - 1);
-                }
-            } else {
-                // invalid tile
-                stack.truncate(stack.len() - 1);
-            }
+        MazeGenerator::new(self, rng).for_each(drop);
+    }
+
+    /// Generates the maze exactly like `populate`, calling `on_progress` with the number of tiles
+    /// finished so far and the number of tiles in the maze every time another 0.5% is finished.
+    ///
+    /// A tile is finished once it has been carved, or it is a wall with passages on more than
+    /// one side of it which the search will never carve, so the count climbs steadily towards
+    /// the total. The last call once the maze is finished always reports every tile.
+    ///
+    /// ```
+    /// use labyrinth::Maze;
+    /// use rand::{rngs::SmallRng, SeedableRng};
+    ///
+    /// let mut maze = Maze::new(101, 101);
+    /// let mut calls = Vec::new();
+    /// maze.populate_with_progress(&mut SmallRng::seed_from_u64(3), |finished, total| {
+    ///     calls.push((finished, total))
+    /// });
+    /// let total = 101 * 101;
+    /// assert_eq!(calls.last(), Some(&(total, total)));
+    ///
+    /// // every call is further along than the last, without any big leaps to the end
+    /// assert!(calls.len() > 100);
+    /// assert!(calls
+    ///     .windows(2)
+    ///     .all(|pair| pair[0].0 < pair[1].0 && pair[1].0 - pair[0].0 <= total / 100));
+    /// ```
+    pub fn populate_with_progress<R, F>(&mut self, rng: &mut R, on_progress: F)
+    where
+        R: Rng + ?Sized,
+        F: FnMut(usize, usize),
+    {
+        MazeGenerator::new(self, rng).finish_with_progress(on_progress);
+    }
+
+    /// Generates the maze like `populate`, but with the search starting from `start` rather than
+    /// a random tile. The rest of the maze is still random, this only pins down where it grows
+    /// out from. Returns an error if `start` is outside of the maze or its mask.
+    ///
+    /// ```
+    /// use labyrinth::Maze;
+    /// use rand::{rngs::SmallRng, SeedableRng};
+    ///
+    /// let generate = |start| {
+    ///     let mut maze = Maze::new(31, 31);
+    ///     maze.populate_from(&mut SmallRng::seed_from_u64(4), start)?;
+    ///     Ok::<_, anyhow::Error>(maze)
+    /// };
+    /// assert_eq!(generate((0, 0))?, generate((0, 0))?);
+    /// assert_ne!(generate((0, 0))?, generate((30, 30))?);
+    /// assert!(generate((31, 0)).is_err());
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn populate_from<R: Rng + ?Sized>(
+        &mut self,
+        rng: &mut R,
+        start: (usize, usize),
+    ) -> anyhow::Result<()> {
+        let start = self.check_start_cell(start)?;
+        MazeGenerator::starting_at(self, rng, start).for_each(drop);
+        Ok(())
+    }
+
+    // the tile the search of populate starts from, if it can start there
+    #[cfg(feature = "std")]
+    fn check_start_cell(&self, (x, y): (usize, usize)) -> anyhow::Result<Coord> {
+        let tile = Coord::try_from((x, y))
+            .ok()
+            .filter(|&tile| self.data.at(tile).is_some());
+        let Some(tile) = tile else {
+            anyhow::bail!(
+                "Cannot start generating from ({}, {}), it is outside of the {}x{} maze.",
+                x,
+                y,
+                self.width,
+                self.height
+            );
+        };
+        if self.is_masked(tile) {
+            anyhow::bail!(
+                "Cannot start generating from ({}, {}), it is outside of the mask.",
+                x,
+                y
+            );
         }
 
-        // first define the start and end positions
-        // go along from top left and bottom right.
-        // on finding a transition Wall -> Empty place the start / end there
-        if let Some(tile) = self.data.iter_mut().find(|tile| *tile == &TileState::Empty) {
-            *tile = TileState::Start;
+        Ok(tile)
+    }
+
+    // the start goes on the first passage tile from the top left, and the end on the last
+    // passage tile which can be reached from it, so the two are never the same tile and the
+    // maze can always be solved. a maze with a single passage tile only gets a start.
+    fn place_start_end(&mut self) {
+        let Some(start) = self.find_tile(TileState::Empty) else {
+            return;
+        };
+
+        let distances = self.distances_from(start);
+        let end = self
+            .positions()
+            .filter(|&tile| tile != start && matches!(distances.at(tile), Some(Some(_))))
+            .last();
+
+        self.set_tile(start, TileState::Start);
+        if let Some(end) = end {
+            self.set_tile(end, TileState::End);
+        }
+    }
+
+    // the lattice based generators treat every tile with even coordinates as a
+    // cell, the tiles between two cells are the walls which get carved away
+    fn cell_dimensions(&self) -> (u32, u32) {
+        (self.width.div_ceil(2), self.height.div_ceil(2))
+    }
+
+    fn random_cell<R: Rng + ?Sized>(&self, rng: &mut R) -> Coord {
+        let (cols, rows) = self.cell_dimensions();
+        Coord(gen_coord(rng, 0..cols) * 2, gen_coord(rng, 0..rows) * 2)
+    }
+
+    // the neighbouring cell in a direction, paired with the wall tile between them. the
+    // lattice never wraps, so both are inside of the maze
+    fn cell_towards(&self, cell: Coord, direction: Direction) -> Option<(Coord, Coord)> {
+        let wall = cell.neighbour(direction, self.width, self.height)?;
+        let next = wall.neighbour(direction, self.width, self.height)?;
+        Some((next, wall))
+    }
+
+    // the in bounds neighbouring cells of a cell, paired with the wall tile between them
+    fn cell_neighbours(&self, cell: Coord) -> Vec<(Coord, Coord)> {
+        Direction::ALL
+            .iter()
+            .filter_map(|&direction| self.cell_towards(cell, direction))
+            .collect()
+    }
+
+    fn carve(&mut self, tile: Coord) {
+        self.set_tile(tile, TileState::Empty);
+    }
+
+    // masked tiles are left alone so nothing can ever be drawn outside of the mask
+    fn set_tile(&mut self, tile: Coord, state: TileState) {
+        if self.is_masked(tile) {
+            return;
+        }
+
+        match self.data.at_mut(tile) {
+            Some(current) if *current != state => *current = state,
+            _ => return,
+        }
+
+        self.generated_by = None;
+        self.origin_shift = None;
+        if state == TileState::Start {
+            self.start = Some(tile);
+        } else if self.start == Some(tile) {
+            self.start = None;
+        }
+        if state == TileState::End {
+            self.end = Some(tile);
+        } else if self.end == Some(tile) {
+            self.end = None;
+        }
+
+        if let Some(recording) = self.recording.as_mut() {
+            recording.record(tile, state);
+        }
+    }
+
+    fn is_masked(&self, tile: Coord) -> bool {
+        matches!(self.mask.at(tile), Some(false))
+    }
+
+    fn is_visited(&self, tile: Coord) -> bool {
+        matches!(self.visited.at(tile), Some(true))
+    }
+
+    fn visit(&mut self, tile: Coord) {
+        if let Some(visited) = self.visited.at_mut(tile) {
+            *visited = true;
         }
+    }
+
+    // a grid of default values the same size as the maze, for the searches to fill in
+    fn blank_tiles<T: Clone + Default>(&self) -> Tiles<T> {
+        let (width, height) = self.data.dimensions();
+        Tiles::new(width, height)
+    }
+
+    // every tile in row order, the order the image is laid out in
+    fn positions(&self) -> impl Iterator<Item = Coord> {
+        row_major(self.width, self.height)
+    }
 
-        if let Some(tile) = self
-            .data
-            .iter_mut()
-            .rev()
-            .find(|tile| *tile == &TileState::Empty)
-        {
-            *tile = TileState::End;
+    fn find_tile(&self, state: TileState) -> Option<Coord> {
+        self.positions()
+            .find(|&tile| self.data.at(tile) == Some(&state))
+    }
+
+    fn is_passage(&self, tile: Coord) -> bool {
+        !matches!(self.data.at(tile), Some(TileState::Wall) | None)
+    }
+
+    /// Whether a passage leads straight from tile `a` into tile `b`, the one question every
+    /// solver and exporter asks when walking the maze. Tiles outside of the maze or the same
+    /// tile are never open.
+    ///
+    /// Both tiles have to be passages, and a crossing of a weave maze only opens north and
+    /// south. Going east or west into a crossing passes underneath it, so from the tile on one
+    /// side of a crossing the one open tile that way is on the far side of it, two tiles along,
+    /// and not the crossing itself. These are the same tiles `open_neighbours` gives. When the
+    /// maze wraps the tiles on opposite edges are next to each other.
+    ///
+    #[cfg_attr(feature = "std", doc = "```")]
+    #[cfg_attr(not(feature = "std"), doc = "```ignore")]
+    /// use labyrinth::Maze;
+    ///
+    /// let maze = Maze::from_ascii("S  \n## \nE  ")?;
+    /// assert!(maze.is_open((0, 0), (1, 0)));
+    /// assert!(maze.is_open((2, 1), (2, 0)));
+    /// // (1, 1) is a wall
+    /// assert!(!maze.is_open((1, 0), (1, 1)));
+    /// // both are passages, but they aren't next to each other
+    /// assert!(!maze.is_open((0, 0), (2, 0)));
+    /// assert!(!maze.is_open((0, 0), (1, 1)));
+    /// assert!(!maze.is_open((2, 0), (3, 0)));
+    ///
+    /// // the passage along the middle row runs under the crossing at (1, 1)
+    /// let weave = Maze::from_ascii("# #\n +S\n#E#")?;
+    /// assert!(weave.is_open((0, 1), (2, 1)));
+    /// assert!(!weave.is_open((0, 1), (1, 1)));
+    /// assert!(!weave.is_open((1, 1), (2, 1)));
+    /// assert!(weave.is_open((1, 1), (1, 0)));
+    /// assert!(weave.is_open((1, 2), (1, 1)));
+    /// let neighbours: Vec<_> = weave.open_neighbours(0, 1).collect();
+    /// assert_eq!(neighbours, [(2, 1)]);
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn is_open(&self, a: (usize, usize), b: (usize, usize)) -> bool {
+        match (Coord::try_from(a), Coord::try_from(b)) {
+            (Ok(a), Ok(b)) => self.is_open_between(a, b),
+            _ => false,
         }
     }
 
+    fn is_open_between(&self, a: Coord, b: Coord) -> bool {
+        self.is_passage(a)
+            && Direction::ALL
+                .iter()
+                .any(|&direction| self.leads_to(a, direction) == Some(b))
+    }
+
+    // the tile a passage on `tile` leads into going in `direction`, which is_open and
+    // passage_neighbours both come down to. `tile` itself isn't checked, so this is also where
+    // a wall would lead once it was carved
+    fn leads_to(&self, tile: Coord, direction: Direction) -> Option<Coord> {
+        let next = self.step(tile, direction)?;
+        let across = matches!(direction, Direction::East | Direction::West);
+        let is_crossing = |tile| self.data.at(tile) == Some(&TileState::Crossing);
+
+        let reached = match (across, is_crossing(tile), is_crossing(next)) {
+            (true, true, _) => return None,
+            (true, false, true) => self.step(next, direction)?,
+            _ => next,
+        };
+        Some(reached).filter(|&reached| self.is_passage(reached))
+    }
+
+    // the passage tiles joined to a tile, walls and the outside of the grid are skipped.
+    // going east or west into a crossing passes underneath it to the tile on the other side.
+    // a maze two tiles across which wraps reaches the same tile both ways, it is only given once
+    fn passage_neighbours(&self, tile: Coord) -> Vec<Coord> {
+        if !self.is_passage(tile) {
+            return Vec::new();
+        }
+
+        let mut neighbours = Vec::new();
+        for &direction in &Direction::ALL {
+            match self.leads_to(tile, direction) {
+                Some(next) if !neighbours.contains(&next) => neighbours.push(next),
+                _ => {}
+            }
+        }
+        neighbours
+    }
+
+    // whether a cell of the lattice is joined to the next cell in `direction` through the wall
+    // between them. passing underneath a crossing joins the cells on either side of it too
+    fn is_joined(&self, cell: Coord, direction: Direction) -> bool {
+        let Some((next, wall)) = self.cell_towards(cell, direction) else {
+            return false;
+        };
+        // into the cell itself, or underneath it to the tile beyond
+        let reaches = |cell, direction| {
+            self.is_open_between(wall, cell)
+                || self
+                    .step(cell, direction)
+                    .is_some_and(|beyond| self.is_open_between(wall, beyond))
+        };
+        reaches(cell, direction.opposite()) && reaches(next, direction)
+    }
+
     // a tile is a valid neighbour if it is surrounded by walls / or one edge
     // and it is unvisited
-    fn is_valid_neighbour(&self, x: usize, y: usize, direction: Direction) -> bool {
+    fn is_valid_neighbour(&self, tile: Coord, direction: Direction) -> bool {
         use Direction::*;
 
         let mut count = 0;
 
-        let xs = x.saturating_sub(1);
-        let ys = y.saturating_sub(1);
+        // tiles past the edge of the grid count as walls, unless the maze wraps around
+        let is_open = |dx: i32, dy: i32| match self.offset(tile, dx, dy) {
+            Some(tile) => !matches!(self.data.at(tile), Some(TileState::Wall) | None),
+            None => false,
+        };
 
         // do right / top / top right
-        if !matches!(self.data.get(x + 1, y), Some(TileState::Wall) | None) && !matches!(direction, West) {
+        if is_open(1, 0) && !matches!(direction, West) {
             count += 1;
         }
 
-        if !matches!(self.data.get(x, y + 1), Some(TileState::Wall) | None) && !matches!(direction, South)
-        {
+        if is_open(0, 1) && !matches!(direction, South) {
             count += 1;
         }
 
-        if !matches!(self.data.get(x + 1, y + 1), Some(TileState::Wall) | None)
-            && !matches!(direction, South | West)
-        {
+        if is_open(1, 1) && !matches!(direction, South | West) {
             count += 1;
         }
 
-        if !matches!(self.data.get(xs, y), Some(TileState::Wall) | None) && !matches!(direction, East)
-        {
+        if is_open(-1, 0) && !matches!(direction, East) {
             count += 1;
         }
 
-        if !matches!(self.data.get(xs, y + 1), Some(TileState::Wall) | None)
-            && !matches!(direction, South | East)
-        {
+        if is_open(-1, 1) && !matches!(direction, South | East) {
             count += 1;
         }
 
         // do bottom and bottom right
-        if !matches!(self.data.get(x, ys), Some(TileState::Wall) | None) && !matches!(direction, North)
-        {
+        if is_open(0, -1) && !matches!(direction, North) {
             count += 1;
         }
 
-        if !matches!(self.data.get(x + 1, ys), Some(TileState::Wall) | None)
-            && !matches!(direction, North | West)
-        {
+        if is_open(1, -1) && !matches!(direction, North | West) {
             count += 1;
         }
 
         // bottom left
-        if !matches!(self.data.get(xs, ys), Some(TileState::Wall) | None)
-            && !matches!(direction, North | East)
-        {
+        if is_open(-1, -1) && !matches!(direction, North | East) {
             count += 1;
         }
 
-        count == 0 && matches!(self.visited.get(x, y), Some(false))
+        count == 0 && matches!(self.visited.at(tile), Some(false)) && !self.is_masked(tile)
+    }
+
+    // a wall with passages around it on more than one side, which is_valid_neighbour turns
+    // down whichever way the search comes at it as only the side it comes in from is ignored
+    fn is_hemmed_in(&self, tile: Coord) -> bool {
+        let open: Vec<_> = (-1..=1)
+            .flat_map(|dy| (-1..=1).map(move |dx| (dx, dy)))
+            .filter(|&offset| offset != (0, 0))
+            .filter(|&(dx, dy)| {
+                self.offset(tile, dx, dy)
+                    .is_some_and(|neighbour| self.is_passage(neighbour))
+            })
+            .collect();
+        let on_one_side = [(1, 0), (-1, 0), (0, 1), (0, -1)].iter().any(|&(sx, sy)| {
+            open.iter()
+                .all(|&(dx, dy)| (sx != 0 && dx == sx) || (sy != 0 && dy == sy))
+        });
+
+        !self.is_passage(tile) && !on_one_side
+    }
+
+    // the tiles next to a tile paired with the direction taken to reach them, written into the
+    // front of `buffer` so nothing is allocated. any direction which would step off the edge of
+    // the grid is skipped, and only the part of the buffer which was filled in is returned
+    fn candidate_neighbours<'b>(
+        &self,
+        tile: Coord,
+        buffer: &'b mut [(Coord, Direction); 4],
+    ) -> &'b mut [(Coord, Direction)] {
+        let mut count = 0;
+        for &direction in Direction::ALL.iter() {
+            if let Some(neighbour) = self.step(tile, direction) {
+                buffer[count] = (neighbour, direction);
+                count += 1;
+            }
+        }
+        &mut buffer[..count]
+    }
+
+    fn step(&self, tile: Coord, direction: Direction) -> Option<Coord> {
+        let (dx, dy) = direction.delta();
+        self.offset(tile, dx, dy)
+    }
+
+    // the tile `dx` across and `dy` up from a tile, wrapping around the edges if the maze does
+    fn offset(&self, tile: Coord, dx: i32, dy: i32) -> Option<Coord> {
+        tile.offset(dx, dy, self.width, self.height, self.wrap)
     }
 
-    pub fn save_to_file<S: AsRef<OsStr> + ?Sized>(&self, s: &S) -> anyhow::Result<()> {
+    /// Saves the maze to a file, picking the format from the file extension.
+    ///
+    /// Supports `.png`, `.svg`, `.txt` (ASCII art), `.json`, `.dot` (a GraphViz graph), `.csv` and
+    /// `.jpg` or `.jpeg`, which are saved at a quality of 90. PNG is better for small mazes, see
+    /// `write_jpeg`.
+    #[cfg(feature = "std")]
+    pub fn save<S: AsRef<OsStr> + ?Sized>(&self, s: &S) -> anyhow::Result<()> {
         let path = Path::new(s);
-        let file = File::create(path)?;
-        let w = &mut BufWriter::new(file);
-
-        let mut encoder = png::Encoder::new(w, self.width, self.height);
-        encoder.set_color(png::ColorType::RGB);
-        encoder.set_depth(png::BitDepth::Eight);
-        let mut writer = encoder
-            .write_header()
-            .context("Failed to write the header of the PNG.")?;
-
-        let data = self
-            .data
+        let extension = path
+            .extension()
+            .and_then(OsStr::to_str)
+            .map(str::to_ascii_lowercase);
+
+        match extension.as_deref() {
+            #[cfg(feature = "render")]
+            Some("png") => self.save_to_file(path),
+            Some("svg") => self.save_svg(path),
+            Some("dot") => self.save_dot(path),
+            Some("txt") => fs::write(path, self.to_ascii() + "\n")
+                .with_context(|| format!("Failed to write the maze to {}.", path.display())),
+            Some("json") => fs::write(path, self.to_json() + "\n")
+                .with_context(|| format!("Failed to write the maze to {}.", path.display())),
+            Some("csv") => fs::write(path, self.to_csv() + "\n")
+                .with_context(|| format!("Failed to write the maze to {}.", path.display())),
+            Some("jpg") | Some("jpeg") => self.save_jpeg(path, jpeg::DEFAULT_JPEG_QUALITY),
+            _ => Err(anyhow::anyhow!(
+                "Unsupported output file {}, expected one of the extensions: \
+                 png, svg, txt, json, dot, csv, jpg.",
+                path.display()
+            )),
+        }
+    }
+
+    /// Loads a maze saved by `save`, picking the format from the file extension.
+    ///
+    /// Supports `.png` (one pixel per tile in the default colours, see `from_png`), `.txt`,
+    /// `.json` and `.csv`. The other formats `save` writes can't be read back.
+    ///
+    /// ```
+    /// use labyrinth::{Maze, MazeBuilder};
+    ///
+    /// let maze = MazeBuilder::new().width(31).height(17).seed(8).build();
+    /// let path = std::env::temp_dir().join("labyrinth_load.json");
+    /// maze.save(&path)?;
+    /// assert_eq!(Maze::load(&path)?, maze);
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn load<S: AsRef<OsStr> + ?Sized>(s: &S) -> anyhow::Result<Maze> {
+        let path = Path::new(s);
+        let extension = path
+            .extension()
+            .and_then(OsStr::to_str)
+            .map(str::to_ascii_lowercase);
+        let read = || {
+            fs::read_to_string(path)
+                .with_context(|| format!("Failed to read the maze from {}.", path.display()))
+        };
+
+        match extension.as_deref() {
+            #[cfg(feature = "render")]
+            Some("png") => Maze::from_png(path),
+            Some("txt") => Maze::from_ascii(&read()?),
+            Some("json") => Maze::from_json(&read()?),
+            Some("csv") => Maze::from_csv(&read()?),
+            _ => Err(anyhow::anyhow!(
+                "Unsupported input file {}, expected one of the extensions: png, txt, json, csv.",
+                path.display()
+            )),
+        }
+    }
+
+    /// The size in pixels of the image produced by `render_scaled`.
+    pub fn scaled_dimensions(&self, scale: u32) -> (u32, u32) {
+        (self.width * scale, self.height * scale)
+    }
+
+    /// Renders the maze to pixels in row order, drawing each tile as a `scale` x `scale` block.
+    pub fn render_scaled(&self, scale: u32) -> Vec<RGB8> {
+        self.upscale_tiles(&self.render_rgb(None), scale, (&TileState::Wall).into())
+    }
+
+    /// Renders the maze to pixels in row order, one pixel per tile, using the colours of `scheme`.
+    pub fn render_with_scheme(&self, scheme: &ColorScheme) -> Vec<RGB8> {
+        self.positions()
+            .map(|tile| scheme.colour(self.data.at(tile).copied().unwrap_or_default()))
+            .collect()
+    }
+
+    /// Renders the maze in the default colours with a border of `margin_px` pixels of `bg` all the
+    /// way round, so the image is `width + 2 * margin_px` by `height + 2 * margin_px` pixels with
+    /// the maze in the middle. Printed mazes look better with a margin.
+    ///
+    /// ```
+    /// use labyrinth::MazeBuilder;
+    /// use rgb::RGB8;
+    ///
+    /// let maze = MazeBuilder::new().width(21).height(11).seed(5).build();
+    /// let grey = RGB8::new(0x80, 0x80, 0x80);
+    /// let pixels = maze.render_with_margin(3, grey);
+    ///
+    /// let (width, height) = (21 + 2 * 3, 11 + 2 * 3);
+    /// assert_eq!(pixels.len(), width * height);
+    /// for &corner in &[0, width - 1, (height - 1) * width, width * height - 1] {
+    ///     assert_eq!(pixels[corner], grey);
+    /// }
+    ///
+    /// // the maze itself starts 3 pixels in and 3 pixels down
+    /// assert_eq!(pixels[3 * width + 3..3 * width + 3 + 21], maze.render_scaled(1)[..21]);
+    /// ```
+    pub fn render_with_margin(&self, margin_px: u32, bg: RGB8) -> Vec<RGB8> {
+        let pixels = self.render_with_scheme(&ColorScheme::default());
+        pad(&pixels, widen(self.width), widen(margin_px), bg)
+    }
+
+    /// Renders the maze to pixels in row order, one pixel per tile, with the dead ends counted
+    /// by `stats` drawn in orange. The start and end keep their colours even at a dead end.
+    ///
+    /// ```
+    /// use labyrinth::MazeBuilder;
+    /// use rgb::RGB8;
+    ///
+    /// let maze = MazeBuilder::new().width(31).height(21).seed(3).braid(0.5).build();
+    /// let orange = RGB8::new(0xFF, 0x8C, 0x00);
+    /// let highlighted = maze.render_dead_ends().iter().filter(|&&p| p == orange).count();
+    ///
+    /// // the start and end are dead ends too, but are drawn in their own colours
+    /// let marked = [maze.start(), maze.end()]
+    ///     .iter()
+    ///     .flatten()
+    ///     .filter(|&&(x, y)| maze.open_neighbours(x, y).count() == 1)
+    ///     .count();
+    /// assert_eq!(highlighted + marked, maze.stats().dead_ends);
+    /// ```
+    pub fn render_dead_ends(&self) -> Vec<RGB8> {
+        self.positions()
+            .map(|position| {
+                let tile = self.data.at(position).copied().unwrap_or_default();
+                if tile == TileState::Empty && self.is_dead_end(position) {
+                    DEAD_END_COLOUR
+                } else {
+                    (&tile).into()
+                }
+            })
+            .collect()
+    }
+
+    /// Renders the maze to grayscale in row order, one byte per tile, see `TileState::luma`.
+    ///
+    #[cfg_attr(feature = "std", doc = "```")]
+    #[cfg_attr(not(feature = "std"), doc = "```ignore")]
+    /// use labyrinth::Maze;
+    ///
+    /// let maze = Maze::from_ascii("S #\n# E").unwrap();
+    /// assert_eq!(maze.render_luma(), [255, 255, 0, 0, 255, 255]);
+    /// ```
+    pub fn render_luma(&self) -> Vec<u8> {
+        self.positions()
+            .map(|tile| self.data.at(tile).copied().unwrap_or_default().luma())
+            .collect()
+    }
+
+    /// Renders the maze to packed 1 bit grayscale in row order, one bit per tile with 0 for walls
+    /// and 1 for everything else. The tiles are packed eight to a byte starting from the most
+    /// significant bit, and each row starts on a fresh byte with the unused bits at the end
+    /// of the row left as 0, the layout of a 1 bit PNG scanline.
+    ///
+    #[cfg_attr(feature = "std", doc = "```")]
+    #[cfg_attr(not(feature = "std"), doc = "```ignore")]
+    /// use labyrinth::Maze;
+    ///
+    /// let maze = Maze::from_ascii("S ########\n#        E").unwrap();
+    /// let packed = maze.render_bilevel();
+    /// assert_eq!(packed.len(), 2 * ((10 + 7) / 8));
+    /// assert_eq!(packed, [0b1100_0000, 0b0000_0000, 0b0111_1111, 0b1100_0000]);
+    /// ```
+    pub fn render_bilevel(&self) -> Vec<u8> {
+        let row_bytes = widen(self.width).div_ceil(8);
+
+        let mut packed = vec![0; row_bytes * widen(self.height)];
+        for tile in self.positions() {
+            let (x, y): (usize, usize) = tile.into();
+            if self.data.at(tile).copied().unwrap_or_default().luma() > 0x7F {
+                packed[y * row_bytes + x / 8] |= 0x80 >> (x % 8);
+            }
+        }
+        packed
+    }
+
+    /// Renders the maze to RGBA pixels in row order, one pixel per tile.
+    ///
+    /// Every tile is opaque unless `transparent_bg` is set, in which case the passages
+    /// get an alpha of 0 while the walls, start and end stay opaque.
+    pub fn render_rgba(&self, transparent_bg: bool) -> Vec<RGBA8> {
+        self.render_rgba_with_scheme(&ColorScheme::default(), transparent_bg)
+    }
+
+    fn render_rgba_with_scheme(&self, scheme: &ColorScheme, transparent_bg: bool) -> Vec<RGBA8> {
+        self.positions()
+            .map(|tile| {
+                let tile = self.data.at(tile).copied().unwrap_or_default();
+                let is_open = matches!(tile, TileState::Empty | TileState::Crossing);
+                let alpha = if transparent_bg && is_open {
+                    0x00_u8
+                } else {
+                    0xFF_u8
+                };
+                scheme.colour(tile).alpha(alpha)
+            })
+            .collect()
+    }
+
+    // lays the tiles out row by row as the image expects, with any tiles
+    // in the overlay drawn as part of the path unless they are the start / end
+    fn render_rgb(&self, overlay: Option<&[Coord]>) -> Vec<RGB8> {
+        let mut on_path: Tiles<bool> = self.blank_tiles();
+        for &tile in overlay.unwrap_or_default() {
+            if let Some(on) = on_path.at_mut(tile) {
+                *on = true;
+            }
+        }
+
+        self.positions()
+            .map(|position| {
+                let tile = self.data.at(position).copied().unwrap_or_default();
+                let is_open = matches!(tile, TileState::Empty | TileState::Crossing);
+                if is_open && on_path.at(position) == Some(&true) {
+                    PATH_COLOUR
+                } else {
+                    (&tile).into()
+                }
+            })
+            .collect()
+    }
+
+    // blows the one pixel per tile image up to `scale`, then draws the sides of the bridge over
+    // each crossing in the wall colour so the east-west passage looks like it goes underneath.
+    // below a scale of 3 there's no room for the sides, so crossings only stand out by colour
+    fn upscale_tiles<P: Copy>(&self, pixels: &[P], scale: u32, wall: P) -> Vec<P> {
+        let (width, scale) = (widen(self.width), widen(scale));
+        let mut scaled = upscale(pixels, width, scale);
+        if scale < 3 {
+            return scaled;
+        }
+
+        for tile in self.positions() {
+            if self.data.at(tile) != Some(&TileState::Crossing) {
+                continue;
+            }
+
+            let (x, y): (usize, usize) = tile.into();
+            for row in y * scale..(y + 1) * scale {
+                let left = row * width * scale + x * scale;
+                scaled[left] = wall;
+                scaled[left + scale - 1] = wall;
+            }
+        }
+
+        scaled
+    }
+}
+
+// blows each pixel of a row ordered image up into a scale x scale block
+fn upscale<P: Copy>(pixels: &[P], width: usize, scale: usize) -> Vec<P> {
+    if width == 0 || scale == 0 {
+        return Vec::new();
+    }
+
+    let mut scaled = Vec::with_capacity(pixels.len() * scale * scale);
+    for row in pixels.chunks(width) {
+        let scaled_row: Vec<P> = row
             .iter()
-            .map(|tile| tile.into())
-            .collect::<Vec<RGB8>>();
+            .flat_map(|&pixel| core::iter::repeat_n(pixel, scale))
+            .collect();
+        for _ in 0..scale {
+            scaled.extend_from_slice(&scaled_row);
+        }
+    }
 
-        writer
-            .write_image_data(data.as_bytes())
-            .context("Failed to write out the image data of the maze.")?;
+    scaled
+}
 
-        Ok(())
+// surrounds a row ordered image with `margin` pixels of `bg` on every side
+fn pad<P: Copy>(pixels: &[P], width: usize, margin: usize, bg: P) -> Vec<P> {
+    if width == 0 || margin == 0 {
+        return pixels.to_vec();
     }
+
+    let padded_width = width + 2 * margin;
+    let mut padded = vec![bg; padded_width * margin];
+    for row in pixels.chunks(width) {
+        padded.extend(core::iter::repeat_n(bg, margin));
+        padded.extend_from_slice(row);
+        padded.extend(core::iter::repeat_n(bg, margin));
+    }
+    padded.extend(core::iter::repeat_n(bg, padded_width * margin));
+
+    padded
 }
 
+// every (x, y) coordinate of an image in row order
+fn row_major(width: u32, height: u32) -> impl Iterator<Item = Coord> {
+    (0..height).flat_map(move |y| (0..width).map(move |x| Coord(x, y)))
+}
+
+#[cfg(feature = "std")]
+pub(crate) fn create_file<S: AsRef<OsStr> + ?Sized>(s: &S) -> anyhow::Result<BufWriter<File>> {
+    let path = Path::new(s);
+    let file = File::create(path)
+        .with_context(|| format!("Failed to create the file {}.", path.display()))?;
+    Ok(BufWriter::new(file))
+}
+
+const PATH_COLOUR: RGB8 = RGB8::new(0x00_u8, 0x00_u8, 0xFF_u8);
+const DEAD_END_COLOUR: RGB8 = RGB8::new(0xFF_u8, 0x8C_u8, 0x00_u8);
+
+/// The algorithms available for generating a maze.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
-enum Direction {
+pub enum Algorithm {
+    RecursiveBacktracker,
+    Prim,
+    Kruskal,
+    Wilson,
+    AldousBroder,
+    RecursiveDivision,
+    Eller,
+    BinaryTree,
+    Sidewinder,
+    HuntAndKill,
+    Weave,
+}
+
+impl Algorithm {
+    pub const ALL: [Algorithm; 11] = [
+        Algorithm::RecursiveBacktracker,
+        Algorithm::Prim,
+        Algorithm::Kruskal,
+        Algorithm::Wilson,
+        Algorithm::AldousBroder,
+        Algorithm::RecursiveDivision,
+        Algorithm::Eller,
+        Algorithm::BinaryTree,
+        Algorithm::Sidewinder,
+        Algorithm::HuntAndKill,
+        Algorithm::Weave,
+    ];
+
+    /// The name used to select the algorithm on the command line.
+    pub fn name(self) -> &'static str {
+        use Algorithm::*;
+        match self {
+            RecursiveBacktracker => "recursive-backtracker",
+            Prim => "prim",
+            Kruskal => "kruskal",
+            Wilson => "wilson",
+            AldousBroder => "aldous-broder",
+            RecursiveDivision => "recursive-division",
+            Eller => "eller",
+            BinaryTree => "binary-tree",
+            Sidewinder => "sidewinder",
+            HuntAndKill => "hunt-and-kill",
+            Weave => "weave",
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl FromStr for Algorithm {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Algorithm::ALL
+            .iter()
+            .copied()
+            .find(|algorithm| algorithm.name() == s)
+            .ok_or_else(|| {
+                let names: Vec<_> = Algorithm::ALL.iter().map(|a| a.name()).collect();
+                anyhow::anyhow!(
+                    "Unknown algorithm {:?}, expected one of: {}.",
+                    s,
+                    names.join(", ")
+                )
+            })
+    }
+}
+
+/// A direction of travel across the maze, north is towards increasing `y`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Direction {
     North,
     South,
     East,
     West,
 }
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
-enum TileState {
+impl Direction {
+    const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    fn opposite(self) -> Direction {
+        match self {
+            Direction::North => Direction::South,
+            Direction::East => Direction::West,
+            Direction::South => Direction::North,
+            Direction::West => Direction::East,
+        }
+    }
+
+    // how far across and up a step in this direction goes
+    pub(crate) fn delta(self) -> (i32, i32) {
+        match self {
+            Direction::North => (0, 1),
+            Direction::East => (1, 0),
+            Direction::South => (0, -1),
+            Direction::West => (-1, 0),
+        }
+    }
+}
+
+/// The contents of a single tile of the maze.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum TileState {
+    #[default]
     Wall,
     Empty,
     Start,
     End,
+    /// A passage where a north-south corridor passes over an east-west one. It can only be
+    /// crossed straight over, going east or west into it passes underneath to the other side.
+    ///
+    #[cfg_attr(feature = "std", doc = "```")]
+    #[cfg_attr(not(feature = "std"), doc = "```ignore")]
+    /// use labyrinth::Maze;
+    ///
+    /// let maze = Maze::from_ascii("### ###\n### ###\nS  +  E\n### ###\n### ###").unwrap();
+    /// let path = maze.solve_bfs().unwrap();
+    /// assert_eq!(path, [(0, 2), (1, 2), (2, 2), (4, 2), (5, 2), (6, 2)]);
+    /// ```
+    Crossing,
+}
+
+impl TileState {
+    /// The brightness the tile is drawn with in a grayscale image, black for walls and white
+    /// for everything else.
+    pub fn luma(self) -> u8 {
+        match self {
+            TileState::Wall => 0x00,
+            TileState::Empty | TileState::Start | TileState::End | TileState::Crossing => 0xFF,
+        }
+    }
 }
 
 impl From<&TileState> for RGB8 {
     fn from(tilestate: &TileState) -> Self {
-        use TileState::*;
-        match tilestate {
-            Wall => RGB8::new(0x00_u8, 0x00_u8, 0x00_u8),
-            Empty => RGB8::new(0xFF_u8, 0xFF_u8, 0xFF_u8),
-            Start => RGB8::new(0x00_u8, 0xFF_u8, 0x00_u8),
-            End => RGB8::new(0xFF_u8, 0x00_u8, 0x00_u8),
+        ColorScheme::default().colour(*tilestate)
+    }
+}
+
+/// The reverse of drawing a tile in the default colours, anything else is an error.
+///
+/// ```
+/// use labyrinth::TileState;
+/// use rgb::RGB8;
+/// use std::convert::TryFrom;
+///
+/// let tiles = [
+///     TileState::Wall,
+///     TileState::Empty,
+///     TileState::Start,
+///     TileState::End,
+///     TileState::Crossing,
+/// ];
+/// for &tile in &tiles {
+///     assert_eq!(TileState::try_from(RGB8::from(&tile)).unwrap(), tile);
+/// }
+/// assert!(TileState::try_from(RGB8::new(0x12, 0x34, 0x56)).is_err());
+/// ```
+#[cfg(feature = "std")]
+impl TryFrom<RGB8> for TileState {
+    type Error = anyhow::Error;
+
+    fn try_from(colour: RGB8) -> Result<Self, Self::Error> {
+        let scheme = ColorScheme::default();
+        [
+            TileState::Wall,
+            TileState::Empty,
+            TileState::Start,
+            TileState::End,
+            TileState::Crossing,
+        ]
+        .iter()
+        .copied()
+        .find(|&tile| scheme.colour(tile) == colour)
+        .with_context(|| {
+            format!(
+                "Unknown colour #{:02x}{:02x}{:02x}, expected one of the tile colours.",
+                colour.r, colour.g, colour.b
+            )
+        })
+    }
+}
+
+/// The colours each kind of tile is drawn in.
+///
+/// The default scheme draws walls black, passages white, the start green and the end red.
+/// The crossings of a weave maze are grey, so they don't look like junctions and come back as
+/// crossings when the image is loaded.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct ColorScheme {
+    pub wall: RGB8,
+    pub empty: RGB8,
+    pub start: RGB8,
+    pub end: RGB8,
+    pub crossing: RGB8,
+}
+
+impl ColorScheme {
+    /// The colour a tile is drawn in.
+    pub fn colour(&self, tile: TileState) -> RGB8 {
+        match tile {
+            TileState::Wall => self.wall,
+            TileState::Empty => self.empty,
+            TileState::Start => self.start,
+            TileState::End => self.end,
+            TileState::Crossing => self.crossing,
+        }
+    }
+
+    /// The same scheme with the wall and passage colours swapped, the start, end and crossings
+    /// are kept.
+    ///
+    #[cfg_attr(feature = "std", doc = "```")]
+    #[cfg_attr(not(feature = "std"), doc = "```ignore")]
+    /// use labyrinth::{ColorScheme, Maze};
+    /// use rgb::RGB8;
+    ///
+    /// let maze = Maze::from_ascii("S #\n# E").unwrap();
+    /// let pixels = maze.render_with_scheme(&ColorScheme::default().inverted());
+    /// assert_eq!(pixels[1], RGB8::new(0x00, 0x00, 0x00));
+    /// assert_eq!(pixels[2], RGB8::new(0xFF, 0xFF, 0xFF));
+    /// ```
+    pub fn inverted(self) -> Self {
+        Self {
+            wall: self.empty,
+            empty: self.wall,
+            ..self
         }
     }
 }
 
-impl Default for TileState {
+impl Default for ColorScheme {
     fn default() -> Self {
-        TileState::Wall
+        Self {
+            wall: RGB8::new(0x00_u8, 0x00_u8, 0x00_u8),
+            empty: RGB8::new(0xFF_u8, 0xFF_u8, 0xFF_u8),
+            start: RGB8::new(0x00_u8, 0xFF_u8, 0x00_u8),
+            end: RGB8::new(0xFF_u8, 0x00_u8, 0x00_u8),
+            crossing: RGB8::new(0xC0_u8, 0xC0_u8, 0xC0_u8),
+        }
     }
 }
+
+/// Parses a colour written as a `#RRGGBB` hex string, e.g. `#1e90ff`.
+#[cfg(feature = "std")]
+pub fn parse_hex_colour(s: &str) -> anyhow::Result<RGB8> {
+    let invalid = || {
+        anyhow::anyhow!(
+            "Invalid colour {:?}, expected a hex colour like #1e90ff.",
+            s
+        )
+    };
+
+    let hex = s.strip_prefix('#').ok_or_else(invalid)?;
+    if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(invalid());
+    }
+
+    let component = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).map_err(|_| invalid());
+    Ok(RGB8::new(component(0)?, component(2)?, component(4)?))
+}
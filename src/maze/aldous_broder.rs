@@ -0,0 +1,51 @@
+use rand::{prelude::SliceRandom, Rng};
+
+use super::{coord::widen, Maze};
+
+impl Maze {
+    /// Generates the maze using the Aldous-Broder algorithm, every spanning tree is equally likely.
+    ///
+    /// ```
+    /// use labyrinth::{Maze, TileState};
+    /// use rand::{rngs::SmallRng, SeedableRng};
+    ///
+    /// // the walk has to find its way to every cell, even down a corridor one tile wide
+    /// for &(width, height) in &[(1, 10), (10, 1), (40, 7), (1, 1)] {
+    ///     let mut maze = Maze::new(width, height);
+    ///     maze.populate_aldous_broder(&mut SmallRng::seed_from_u64(3));
+    ///
+    ///     let mut cells = (0..height as usize)
+    ///         .step_by(2)
+    ///         .flat_map(|y| (0..width as usize).step_by(2).map(move |x| (x, y)));
+    ///     assert!(cells.all(|(x, y)| maze.tile(x, y) != Some(TileState::Wall)));
+    ///     assert!(maze.is_perfect());
+    /// }
+    /// ```
+    pub fn populate_aldous_broder<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        let (cols, rows) = self.cell_dimensions();
+        let mut remaining = widen(cols) * widen(rows) - 1;
+
+        let mut current = self.random_cell(rng);
+        self.carve(current);
+        self.visit(current);
+
+        while remaining > 0 {
+            let neighbours = self.cell_neighbours(current);
+            let Some(&(next, wall)) = neighbours.choose(rng) else {
+                break;
+            };
+
+            // only carve when stepping into a cell the walk hasn't been to yet
+            if !self.is_visited(next) {
+                self.carve(wall);
+                self.carve(next);
+                self.visit(next);
+                remaining -= 1;
+            }
+
+            current = next;
+        }
+
+        self.place_start_end();
+    }
+}
@@ -0,0 +1,270 @@
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::{convert::TryFrom, ffi::OsStr, io::Write, iter};
+
+#[cfg(feature = "std")]
+use anyhow::Context;
+#[cfg(feature = "std")]
+use grid::Grid;
+#[cfg(feature = "std")]
+use rand::Rng;
+#[cfg(feature = "std")]
+use rgb::RGB8;
+
+#[cfg(feature = "std")]
+use super::{create_file, widen, Algorithm};
+use super::{row_major, Coord, Maze, TileState, Tiles};
+
+/// The frames of a maze being generated, see `MazeBuilder::try_build_frames`.
+///
+/// Only the first frame is kept in full, every other frame is kept as the tiles which changed
+/// since the one before, so recording a large maze costs about as much as the maze itself.
+/// The frames are put back together one at a time by `iter`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frames {
+    first: Tiles<TileState>,
+    // the tiles after every change so far, which is where the next frame is taken from
+    last: Tiles<TileState>,
+    changes: Vec<(Coord, TileState)>,
+    // how many of the changes make up each frame after the first
+    ends: Vec<usize>,
+}
+
+impl Frames {
+    pub(super) fn new(first: &Tiles<TileState>) -> Self {
+        Self {
+            first: first.clone(),
+            last: first.clone(),
+            changes: Vec::new(),
+            ends: Vec::new(),
+        }
+    }
+
+    fn change(&mut self, tile: Coord, state: TileState) {
+        if let Some(last) = self.last.at_mut(tile) {
+            *last = state;
+        }
+        self.changes.push((tile, state));
+    }
+
+    // ends a frame if anything has changed since the one before
+    fn end_frame(&mut self) {
+        if self.ends.last().copied().unwrap_or(0) < self.changes.len() {
+            self.ends.push(self.changes.len());
+        }
+    }
+
+    // adds `tiles` as the last frame, unless that's how the frames already end
+    pub(super) fn finish(&mut self, tiles: &Tiles<TileState>) {
+        self.end_frame();
+        if *tiles == self.last {
+            return;
+        }
+
+        let (width, height) = tiles.dimensions();
+        for tile in row_major(width, height) {
+            if let Some(&state) = tiles
+                .at(tile)
+                .filter(|&state| self.last.at(tile) != Some(state))
+            {
+                self.change(tile, state);
+            }
+        }
+        self.end_frame();
+    }
+
+    // the same frames with every frame passed through `frame` and every changed tile through
+    // `tile`, which have to agree on where each tile ends up
+    pub(super) fn map<F, G>(self, frame: F, tile: G) -> Self
+    where
+        F: Fn(&Tiles<TileState>) -> Tiles<TileState>,
+        G: Fn(Coord) -> Coord,
+    {
+        Self {
+            first: frame(&self.first),
+            last: frame(&self.last),
+            changes: self
+                .changes
+                .into_iter()
+                .map(|(changed, state)| (tile(changed), state))
+                .collect(),
+            ends: self.ends,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Frames {
+    /// Puts the frames back together one at a time, the first is the maze before generating
+    /// and the last is the finished maze.
+    ///
+    /// ```
+    /// use labyrinth::MazeBuilder;
+    ///
+    /// let builder = MazeBuilder::new().width(21).height(15).seed(4);
+    /// let (_, frames) = builder.try_build_frames(10).unwrap();
+    /// let (_, recording) = builder.try_build_recording(10).unwrap();
+    ///
+    /// assert!(frames.iter().count() > 2);
+    /// assert!(frames.iter().eq(recording));
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = Grid<TileState>> + '_ {
+        let mut tiles = self.first.clone();
+        let mut start = 0;
+        let rest = self.ends.iter().map(move |&end| {
+            for &(tile, state) in &self.changes[start..end] {
+                if let Some(to) = tiles.at_mut(tile) {
+                    *to = state;
+                }
+            }
+            start = end;
+            tiles.to_grid()
+        });
+        iter::once(self.first.to_grid()).chain(rest)
+    }
+
+    /// Saves the frames as an animated GIF like `Maze::save_gif`, without ever holding more
+    /// than one of them in full.
+    pub fn save_gif<S: AsRef<OsStr> + ?Sized>(&self, s: &S, delay_ms: u16) -> anyhow::Result<()> {
+        self.write_gif(create_file(s)?, delay_ms)
+    }
+
+    /// Writes the frames as an animated GIF to any writer like `Maze::write_gif`.
+    pub fn write_gif<W: Write>(&self, w: W, delay_ms: u16) -> anyhow::Result<()> {
+        let (width, height) = self.first.dimensions();
+        write_frames(w, (widen(width), widen(height)), self.iter(), delay_ms)
+    }
+}
+
+// the snapshots taken while a maze is being generated
+#[derive(Debug, Clone)]
+pub(super) struct Recording {
+    every: usize,
+    changes: usize,
+    frames: Frames,
+}
+
+impl Recording {
+    // called after every change to a tile, ending a frame after every `every` changes
+    pub(super) fn record(&mut self, tile: Coord, state: TileState) {
+        self.frames.change(tile, state);
+        self.changes += 1;
+        if self.changes.is_multiple_of(self.every) {
+            self.frames.end_frame();
+        }
+    }
+}
+
+impl Maze {
+    // runs a generator while keeping a frame after every `every` changes to the tiles
+    pub(super) fn record<F: FnOnce(&mut Maze)>(&mut self, every: usize, generate: F) -> Frames {
+        self.recording = Some(Recording {
+            every: every.max(1),
+            changes: 0,
+            frames: Frames::new(&self.data),
+        });
+
+        generate(self);
+
+        let mut frames = self
+            .recording
+            .take()
+            .map_or_else(|| Frames::new(&self.data), |r| r.frames);
+        frames.finish(&self.data);
+        frames
+    }
+}
+
+#[cfg(feature = "std")]
+impl Maze {
+    /// Generates the maze with `populate`, returning a snapshot of the tiles after every one
+    /// that is carved. The first frame is the solid maze and the last is the finished maze.
+    pub fn populate_recording<R: Rng + ?Sized>(&mut self, rng: &mut R) -> Vec<Grid<TileState>> {
+        self.populate_recording_with(Algorithm::RecursiveBacktracker, rng, 1)
+    }
+
+    /// Generates the maze with any algorithm, returning a snapshot of the tiles after every
+    /// `every` changes. Every snapshot is a full copy of the maze, `MazeBuilder::try_build_frames`
+    /// keeps only what changed for large mazes. The first frame is the maze before generating
+    /// and the last is always the finished maze.
+    pub fn populate_recording_with<R: Rng + ?Sized>(
+        &mut self,
+        algorithm: Algorithm,
+        rng: &mut R,
+        every: usize,
+    ) -> Vec<Grid<TileState>> {
+        self.record(every, |maze| maze.populate_with(algorithm, rng))
+            .iter()
+            .collect()
+    }
+
+    /// Saves the frames from `populate_recording` as an animated GIF, one pixel per tile,
+    /// showing each frame for `delay_ms` milliseconds.
+    pub fn save_gif<S: AsRef<OsStr> + ?Sized>(
+        frames: &[Grid<TileState>],
+        s: &S,
+        delay_ms: u16,
+    ) -> anyhow::Result<()> {
+        Self::write_gif(frames, create_file(s)?, delay_ms)
+    }
+
+    /// Writes the frames from `populate_recording` as an animated GIF to any writer.
+    pub fn write_gif<W: Write>(
+        frames: &[Grid<TileState>],
+        w: W,
+        delay_ms: u16,
+    ) -> anyhow::Result<()> {
+        let Some(first) = frames.first() else {
+            anyhow::bail!("Cannot make a GIF without any frames.");
+        };
+
+        let (width, height) = first.size();
+        if frames.iter().any(|frame| frame.size() != (width, height)) {
+            anyhow::bail!("Cannot make a GIF from frames of different sizes.");
+        }
+        write_frames(w, (width, height), frames.iter().cloned(), delay_ms)
+    }
+}
+
+// writes frames which are all `width` by `height` as an animated GIF
+#[cfg(feature = "std")]
+fn write_frames<W, I>(
+    w: W,
+    (width, height): (usize, usize),
+    frames: I,
+    delay_ms: u16,
+) -> anyhow::Result<()>
+where
+    W: Write,
+    I: Iterator<Item = Grid<TileState>>,
+{
+    let (Ok(gif_width), Ok(gif_height)) = (u16::try_from(width), u16::try_from(height)) else {
+        anyhow::bail!(
+            "Cannot make a GIF of a {}x{} maze, GIFs are at most 65535 pixels across.",
+            width,
+            height
+        );
+    };
+
+    // the palette is in the same order as the variants of TileState
+    let palette: Vec<RGB8> = [
+        TileState::Wall,
+        TileState::Empty,
+        TileState::Start,
+        TileState::End,
+        TileState::Crossing,
+    ]
+    .iter()
+    .map(RGB8::from)
+    .collect();
+
+    let indices = frames.map(|frame| {
+        (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| frame.get(x, y).copied().unwrap_or_default() as u8)
+            .collect()
+    });
+
+    crate::gif::write(w, gif_width, gif_height, &palette, indices, delay_ms / 10)
+        .context("Failed to write out the GIF.")
+}
@@ -0,0 +1,58 @@
+use std::thread;
+
+use super::{Maze, MazeBuilder};
+
+impl Maze {
+    /// Generates `count` mazes spread across every available core, the `i`th maze is seeded
+    /// with `base_seed + i` so a batch is the same as generating each maze one at a time with
+    /// `MazeBuilder::seed`, no matter how many threads there are.
+    ///
+    /// ```
+    /// use labyrinth::{Maze, MazeBuilder};
+    ///
+    /// let batch = Maze::generate_batch(37, 21, 15, 5);
+    /// let one_at_a_time: Vec<Maze> = (5..42)
+    ///     .map(|seed| MazeBuilder::new().width(21).height(15).seed(seed).build())
+    ///     .collect();
+    /// assert_eq!(batch, one_at_a_time);
+    /// assert!(Maze::generate_batch(0, 21, 15, 5).is_empty());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if either the width or height is zero.
+    pub fn generate_batch(count: usize, width: u32, height: u32, base_seed: u64) -> Vec<Maze> {
+        let builder = MazeBuilder::new().width(width).height(height);
+        let threads = thread::available_parallelism().map_or(1, |n| n.get());
+        let per_thread = count.div_ceil(threads).max(1);
+
+        // every maze has its own rng, so the threads never share any state
+        let seeds: Vec<u64> = (0..count as u64)
+            .map(|i| base_seed.wrapping_add(i))
+            .collect();
+        thread::scope(|scope| {
+            let handles: Vec<_> = seeds
+                .chunks(per_thread)
+                .map(|seeds| {
+                    let builder = &builder;
+                    scope.spawn(move || {
+                        seeds
+                            .iter()
+                            .map(|&seed| builder.clone().seed(seed).build())
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+
+            // joining in order keeps the mazes in seed order
+            handles
+                .into_iter()
+                .flat_map(|handle| {
+                    handle
+                        .join()
+                        .unwrap_or_else(|e| std::panic::resume_unwind(e))
+                })
+                .collect()
+        })
+    }
+}
@@ -0,0 +1,86 @@
+use alloc::vec::Vec;
+
+use rand::{prelude::SliceRandom, Rng};
+
+use super::{Coord, Direction, Maze};
+
+impl Maze {
+    /// Generates the maze using the binary tree algorithm,
+    /// each cell is joined to the cell north or east of it.
+    ///
+    /// North is towards the last row of tiles. The cells along the north edge can only be
+    /// joined east and the ones down the east edge only north, so both edges are one long
+    /// corridor.
+    ///
+    /// ```
+    /// use labyrinth::{Maze, TileState};
+    /// use rand::{rngs::SmallRng, SeedableRng};
+    ///
+    /// for seed in 0..4 {
+    ///     let mut maze = Maze::new(31, 21);
+    ///     maze.populate_binary_tree(&mut SmallRng::seed_from_u64(seed));
+    ///
+    ///     let open = |x, y| maze.tile(x, y) != Some(TileState::Wall);
+    ///     assert!((0..31).all(|x| open(x, 20)));
+    ///     assert!((0..21).all(|y| open(30, y)));
+    ///     assert!(maze.is_perfect());
+    /// }
+    /// ```
+    pub fn populate_binary_tree<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        let (cols, rows) = self.cell_dimensions();
+
+        for cy in 0..rows {
+            for cx in 0..cols {
+                let cell = Coord(cx * 2, cy * 2);
+                self.carve(cell);
+                self.visit(cell);
+
+                // along the north edge and east edge only one of the choices is available,
+                // which is what leaves those edges as single long corridors
+                let choices: Vec<_> = [Direction::North, Direction::East]
+                    .iter()
+                    .filter_map(|&direction| self.cell_towards(cell, direction))
+                    .collect();
+                if let Some(&(_, wall)) = choices.choose(rng) {
+                    self.carve(wall);
+                }
+            }
+        }
+
+        self.place_start_end();
+    }
+
+    /// Generates the maze using the sidewinder algorithm, each row is split into runs
+    /// of cells joined east to west, with one passage north out of each run.
+    pub fn populate_sidewinder<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        let (cols, rows) = self.cell_dimensions();
+
+        for cy in 0..rows {
+            let mut run = Vec::new();
+            for cx in 0..cols {
+                let cell = Coord(cx * 2, cy * 2);
+                self.carve(cell);
+                self.visit(cell);
+                run.push(cell);
+
+                let north_edge = cy + 1 == rows;
+                let east_edge = cx + 1 == cols;
+
+                // the north edge can't close a run so it becomes one long corridor
+                if east_edge || (!north_edge && rng.gen()) {
+                    if let Some((_, wall)) = run
+                        .choose(rng)
+                        .and_then(|&member| self.cell_towards(member, Direction::North))
+                    {
+                        self.carve(wall);
+                    }
+                    run.clear();
+                } else if let Some((_, wall)) = self.cell_towards(cell, Direction::East) {
+                    self.carve(wall);
+                }
+            }
+        }
+
+        self.place_start_end();
+    }
+}
@@ -0,0 +1,169 @@
+use alloc::vec::Vec;
+
+use rand::{prelude::SliceRandom, Rng};
+
+use super::{Coord, Direction, Maze, TileState};
+
+impl Maze {
+    /// Removes a `fraction` of the dead ends by knocking out a wall from each one into a
+    /// neighbouring passage, adding loops to the maze. Meant to run after the maze is populated.
+    ///
+    /// A `fraction` of 0 leaves the maze unchanged and 1 removes every dead end, fractions outside
+    /// of that range are clamped. A dead end without another passage on the far side of any of
+    /// its walls is tunnelled on through the walls around it until it joins up with one, so
+    /// only a dead end with no walls left to carve, like the ends of a maze one tile wide, stays.
+    ///
+    /// ```
+    /// use labyrinth::MazeBuilder;
+    /// use rand::{rngs::SmallRng, SeedableRng};
+    ///
+    /// let mut maze = MazeBuilder::new().width(40).height(30).seed(3).build();
+    /// assert!(maze.stats().dead_ends > 0);
+    ///
+    /// maze.braid(&mut SmallRng::seed_from_u64(3), 1.0);
+    /// assert_eq!(maze.stats().dead_ends, 0);
+    /// assert!(maze.solve_bfs().is_some());
+    /// ```
+    pub fn braid<R: Rng + ?Sized>(&mut self, rng: &mut R, fraction: f64) {
+        let mut dead_ends: Vec<_> = self
+            .positions()
+            .filter(|&tile| self.is_dead_end(tile))
+            .collect();
+        dead_ends.shuffle(rng);
+
+        // rounded half away from zero by hand, as f64::round needs std. NaN clamps to NaN which
+        // saturates to 0 in the cast and isn't at least a half
+        let scaled = fraction.clamp(0.0, 1.0) * dead_ends.len() as f64;
+        let whole = scaled as usize;
+        let count = whole + usize::from(scaled - whole as f64 >= 0.5);
+        for tile in dead_ends.into_iter().take(count) {
+            // opening up an earlier dead end may have already joined this one into a loop. a
+            // wall without a passage behind it becomes the new dead end, so keep going from it
+            let mut tile = tile;
+            while self.is_dead_end(tile) {
+                let mut walls = self.braid_walls(tile);
+                if walls.is_empty() {
+                    walls = self.closed_walls(tile);
+                }
+
+                let Some(&wall) = walls.choose(rng) else {
+                    break;
+                };
+                self.carve(wall);
+                tile = wall;
+            }
+        }
+    }
+
+    /// Adds `extra_edges` loops to the maze by knocking out walls which have a passage on either
+    /// side of them, each opening joining two passages which were already connected. Returns how
+    /// many were added, which is only fewer than `extra_edges` once there are no walls left.
+    ///
+    /// Unlike `braid` this controls how loopy the maze gets overall rather than which dead ends
+    /// go. Each opening is a different wall and only walls with nothing open on their other two
+    /// sides are used, so every opening makes exactly one new loop.
+    ///
+    #[cfg_attr(feature = "std", doc = "```")]
+    #[cfg_attr(not(feature = "std"), doc = "```ignore")]
+    /// use labyrinth::{Maze, MazeBuilder, TileState};
+    /// use rand::{rngs::SmallRng, SeedableRng};
+    ///
+    /// // the passages and the steps between them, each step is counted from both of its ends
+    /// let count = |maze: &Maze| {
+    ///     let (width, height) = maze.dimensions();
+    ///     let (width, height) = (width as usize, height as usize);
+    ///     let (mut passages, mut ends) = (0, 0);
+    ///     for (x, y) in (0..height).flat_map(|y| (0..width).map(move |x| (x, y))) {
+    ///         if maze.tile(x, y) != Some(TileState::Wall) {
+    ///             passages += 1;
+    ///             ends += maze.open_neighbours(x, y).count();
+    ///         }
+    ///     }
+    ///     (passages, ends / 2)
+    /// };
+    ///
+    /// let mut maze = MazeBuilder::new().width(41).height(41).seed(6).build();
+    /// let (passages, edges) = count(&maze);
+    /// assert_eq!(maze.braid_to_density(&mut SmallRng::seed_from_u64(6), 10), 10);
+    ///
+    /// // every opening is one new passage with a step to either side of it
+    /// let (new_passages, new_edges) = count(&maze);
+    /// assert_eq!(new_passages, passages + 10);
+    /// assert_eq!(new_edges, edges + 20);
+    ///
+    /// let (x, y) = maze.start().unwrap();
+    /// let reachable = maze.reachable_from(x, y);
+    /// assert_eq!(reachable.iter().filter(|&&r| r).count(), new_passages);
+    /// ```
+    pub fn braid_to_density<R: Rng + ?Sized>(&mut self, rng: &mut R, extra_edges: usize) -> usize {
+        let mut walls: Vec<_> = self
+            .positions()
+            .filter(|&tile| self.is_loop_wall(tile))
+            .collect();
+        walls.shuffle(rng);
+
+        let mut added = 0;
+        for wall in walls {
+            if added == extra_edges {
+                break;
+            }
+
+            // an earlier opening next to this wall would make it join up more than two passages
+            if self.is_loop_wall(wall) {
+                self.carve(wall);
+                added += 1;
+            }
+        }
+
+        added
+    }
+
+    // a wall with a passage on two opposite sides and nothing open on the other two, so carving
+    // it adds exactly one loop. crossings are left out as they can only be entered one way
+    fn is_loop_wall(&self, tile: Coord) -> bool {
+        if self.is_passage(tile) || self.is_masked(tile) {
+            return false;
+        }
+
+        let side = |direction| self.leads_to(tile, direction);
+        let plain = |tile: Coord| self.data.at(tile) != Some(&TileState::Crossing);
+        match (
+            side(Direction::North),
+            side(Direction::South),
+            side(Direction::East),
+            side(Direction::West),
+        ) {
+            (Some(a), Some(b), None, None) | (None, None, Some(a), Some(b)) => {
+                a != b && plain(a) && plain(b)
+            }
+            _ => false,
+        }
+    }
+
+    // a passage tile with only one way out of it
+    pub(super) fn is_dead_end(&self, tile: Coord) -> bool {
+        self.is_passage(tile) && self.passage_neighbours(tile).len() == 1
+    }
+
+    // the walls next to a tile which would lead into another passage behind them once carved
+    fn braid_walls(&self, tile: Coord) -> Vec<Coord> {
+        Direction::ALL
+            .iter()
+            .filter_map(|&direction| {
+                let wall = self.step(tile, direction)?;
+                let behind = self.leads_to(wall, direction);
+                let closed = !self.is_passage(wall) && !self.is_masked(wall);
+                (closed && behind.is_some()).then_some(wall)
+            })
+            .collect()
+    }
+
+    // the walls next to a tile which can be carved
+    fn closed_walls(&self, tile: Coord) -> Vec<Coord> {
+        Direction::ALL
+            .iter()
+            .filter_map(|&direction| self.step(tile, direction))
+            .filter(|&wall| !self.is_passage(wall) && !self.is_masked(wall))
+            .collect()
+    }
+}
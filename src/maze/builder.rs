@@ -0,0 +1,455 @@
+#[cfg(feature = "std")]
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+
+#[cfg(feature = "std")]
+use grid::Grid;
+use rand::SeedableRng;
+
+use super::{
+    animate::Frames, coord::widen, rng::PortableRng, row_major, Algorithm, Coord, Maze,
+    MazeGenerator, Symmetry, TileState, Tiles,
+};
+
+/// Configures and generates a maze in one go.
+///
+/// ```
+/// use labyrinth::{Algorithm, MazeBuilder};
+///
+/// let builder = MazeBuilder::new()
+///     .width(81)
+///     .height(61)
+///     .seed(42)
+///     .algorithm(Algorithm::Prim)
+///     .braid(0.3);
+/// let maze = builder.build();
+/// assert_eq!(maze.to_ascii().lines().count(), 61);
+///
+/// // the same settings with the same seed build the same maze every time
+/// assert_eq!(builder.build(), builder.build());
+/// assert_ne!(builder.build(), builder.clone().seed(43).build());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct MazeBuilder {
+    width: u32,
+    height: u32,
+    seed: Option<u64>,
+    algorithm: Algorithm,
+    braid: f64,
+    hardest: bool,
+    wrap: bool,
+    symmetry: Option<Symmetry>,
+    border_entrances: bool,
+    solid_border: bool,
+    start_cell: Option<(usize, usize)>,
+}
+
+impl Default for MazeBuilder {
+    fn default() -> Self {
+        Self {
+            width: 500,
+            height: 500,
+            seed: None,
+            algorithm: Algorithm::RecursiveBacktracker,
+            braid: 0.0,
+            hardest: false,
+            wrap: false,
+            symmetry: None,
+            border_entrances: false,
+            solid_border: false,
+            start_cell: None,
+        }
+    }
+}
+
+impl MazeBuilder {
+    /// A 500x500 maze generated by the recursive backtracker from a random seed.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The width of the maze in tiles, each tile is one pixel in an unscaled PNG.
+    pub fn width(mut self, width: u32) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// The height of the maze in tiles, each tile is one pixel in an unscaled PNG.
+    pub fn height(mut self, height: u32) -> Self {
+        self.height = height;
+        self
+    }
+
+    /// Seeds the RNG so the same maze is generated every time, without a seed it is random.
+    /// Without the `std` feature there is nowhere to get a random seed from, so an unseeded
+    /// builder always uses a seed of 0.
+    ///
+    /// A seed gives the same maze on every platform, down to the bytes of the PNG.
+    ///
+    #[cfg_attr(feature = "render", doc = "```")]
+    #[cfg_attr(not(feature = "render"), doc = "```ignore")]
+    /// use labyrinth::MazeBuilder;
+    ///
+    /// let maze = MazeBuilder::new().width(31).height(17).seed(42).build();
+    /// let mut png = Vec::new();
+    /// maze.write_png(&mut png)?;
+    ///
+    /// // 64 bit FNV-1a, any hash which doesn't change between runs will do
+    /// let hash = png.iter().fold(0xcbf2_9ce4_8422_2325_u64, |hash, &byte| {
+    ///     (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    /// });
+    /// assert_eq!(hash, 0xc225_ef6f_df48_f453);
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    pub fn algorithm(mut self, algorithm: Algorithm) -> Self {
+        self.algorithm = algorithm;
+        self
+    }
+
+    /// The fraction of dead ends to remove after generating, see `Maze::braid`.
+    pub fn braid(mut self, fraction: f64) -> Self {
+        self.braid = fraction;
+        self
+    }
+
+    /// Moves the start and end onto the ends of the longest path,
+    /// see `Maze::place_hardest_start_end`.
+    pub fn hardest(mut self, hardest: bool) -> Self {
+        self.hardest = hardest;
+        self
+    }
+
+    /// Joins the edges of the maze up with the opposite edges, see `Maze::set_wrap`.
+    pub fn wrap(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    /// Moves the start and end onto the edges of the maze, see `Maze::place_border_entrances`.
+    pub fn border_entrances(mut self, border_entrances: bool) -> Self {
+        self.border_entrances = border_entrances;
+        self
+    }
+
+    /// Keeps the outermost ring of tiles as wall, generating the maze inside of it instead.
+    /// The ring is masked off so braiding can't open it up either. `try_build` returns an
+    /// error if the maze is smaller than 3x3, or it wraps or has border entrances.
+    ///
+    #[cfg_attr(feature = "std", doc = "```")]
+    #[cfg_attr(not(feature = "std"), doc = "```ignore")]
+    /// use labyrinth::{Algorithm, MazeBuilder, TileState};
+    ///
+    /// for &algorithm in &[Algorithm::RecursiveBacktracker, Algorithm::Kruskal] {
+    ///     let maze = MazeBuilder::new()
+    ///         .width(30)
+    ///         .height(20)
+    ///         .seed(7)
+    ///         .algorithm(algorithm)
+    ///         .braid(1.0)
+    ///         .solid_border(true)
+    ///         .try_build()?;
+    ///
+    ///     let ring = (0..30).flat_map(|x| vec![(x, 0), (x, 19)]);
+    ///     let ring = ring.chain((0..20).flat_map(|y| vec![(0, y), (29, y)]));
+    ///     for (x, y) in ring {
+    ///         assert_eq!(maze.tile(x, y), Some(TileState::Wall));
+    ///     }
+    ///     assert!(maze.solution().is_some());
+    /// }
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn solid_border(mut self, solid_border: bool) -> Self {
+        self.solid_border = solid_border;
+        self
+    }
+
+    /// Generates the maze with the given symmetry, see `Maze::populate_symmetric`.
+    pub fn symmetry(mut self, symmetry: Symmetry) -> Self {
+        self.symmetry = Some(symmetry);
+        self
+    }
+
+    /// Starts the search of the recursive backtracker from `start` instead of a random tile,
+    /// see `Maze::populate_from`. `try_build` returns an error if `start` is outside of the
+    /// maze or another algorithm or a symmetry is picked.
+    pub fn start_cell(mut self, start: (usize, usize)) -> Self {
+        self.start_cell = Some(start);
+        self
+    }
+
+    /// Generates the maze.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either the width or height is zero, or less than three with a solid border,
+    /// see `try_build` for a fallible version.
+    pub fn build(&self) -> Maze {
+        self.generate(None, None).0
+    }
+
+    /// Generates the maze, returning an error if either the width or height is zero.
+    #[cfg(feature = "std")]
+    pub fn try_build(&self) -> anyhow::Result<Maze> {
+        self.check()?;
+        Ok(self.build())
+    }
+
+    /// Generates the maze along with a snapshot of the tiles after every `every` changes,
+    /// see `Maze::populate_recording_with`. The last frame is always the finished maze.
+    #[cfg(feature = "std")]
+    pub fn try_build_recording(
+        &self,
+        every: usize,
+    ) -> anyhow::Result<(Maze, Vec<Grid<TileState>>)> {
+        let (maze, frames) = self.try_build_frames(every)?;
+        Ok((maze, frames.iter().collect()))
+    }
+
+    /// Generates the maze along with a frame after every `every` changes like
+    /// `try_build_recording`, keeping only the tiles which changed between frames.
+    #[cfg(feature = "std")]
+    pub fn try_build_frames(&self, every: usize) -> anyhow::Result<(Maze, Frames)> {
+        self.check()?;
+        let (maze, frames) = self.generate(Some(every), None);
+        let frames = frames.unwrap_or_else(|| Frames::new(&maze.data));
+        Ok((maze, frames))
+    }
+
+    /// Generates the maze, reporting how far along it is to `on_progress` as
+    /// `Maze::populate_with_progress` does. Only the recursive backtracker without a symmetry
+    /// reports while carving, everything else just reports every tile once it's finished.
+    #[cfg(feature = "std")]
+    pub fn try_build_with_progress<F: FnMut(usize, usize)>(
+        &self,
+        mut on_progress: F,
+    ) -> anyhow::Result<Maze> {
+        self.check()?;
+        Ok(self.generate(None, Some(&mut on_progress)).0)
+    }
+
+    // everything the try_ builds can catch before generating anything
+    #[cfg(feature = "std")]
+    fn check(&self) -> anyhow::Result<()> {
+        let maze = Maze::try_new(self.width, self.height)?;
+        if let Some(start) = self.start_cell {
+            if self.algorithm != Algorithm::RecursiveBacktracker || self.symmetry.is_some() {
+                anyhow::bail!(
+                    "A start cell can only be used with the recursive backtracker and no symmetry."
+                );
+            }
+            maze.check_start_cell(start)?;
+        }
+        if self.solid_border {
+            if self.width < 3 || self.height < 3 {
+                anyhow::bail!(
+                    "Cannot give a {}x{} maze a solid border, it must be at least 3x3.",
+                    self.width,
+                    self.height
+                );
+            }
+            if self.wrap || self.border_entrances {
+                anyhow::bail!("A solid border can't be used with wrap or border entrances.");
+            }
+            if let Some((x, y)) = self.start_cell {
+                let inside = Coord::try_from((x, y))
+                    .is_ok_and(|tile| is_inside_border(tile, (self.width, self.height)));
+                if !inside {
+                    anyhow::bail!(
+                        "Cannot start generating from ({}, {}), it is on the solid border.",
+                        x,
+                        y
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn generate(
+        &self,
+        every: Option<usize>,
+        on_progress: Option<&mut dyn FnMut(usize, usize)>,
+    ) -> (Maze, Option<Frames>) {
+        // even a random maze is given a seed, so it can be written into the PNG
+        #[cfg(feature = "std")]
+        let seed = self.seed.unwrap_or_else(rand::random);
+        #[cfg(not(feature = "std"))]
+        let seed = self.seed.unwrap_or_default();
+        let mut rng = PortableRng::seed_from_u64(seed);
+
+        // a solid border is a maze two tiles smaller generated on its own and then walled in,
+        // which keeps the lattice based generators lined up with the inside of the border
+        let (width, height, start_cell) = if self.solid_border {
+            let start_cell = self
+                .start_cell
+                .map(|(x, y)| (x.saturating_sub(1), y.saturating_sub(1)));
+            (
+                self.width.saturating_sub(2),
+                self.height.saturating_sub(2),
+                start_cell,
+            )
+        } else {
+            (self.width, self.height, self.start_cell)
+        };
+
+        let mut maze = Maze::new(width, height);
+        maze.set_wrap(self.wrap);
+        let populate = |maze: &mut Maze, rng: &mut PortableRng| {
+            if self.algorithm == Algorithm::RecursiveBacktracker && self.symmetry.is_none() {
+                // check has already made sure the start cell is inside of the maze
+                let generator = match start_cell.map(Coord::try_from) {
+                    Some(Ok(start)) => MazeGenerator::starting_at(maze, rng, start),
+                    _ => MazeGenerator::new(maze, rng),
+                };
+                match on_progress {
+                    Some(on_progress) => generator.finish_with_progress(on_progress),
+                    None => generator.for_each(drop),
+                }
+                return;
+            }
+
+            match self.symmetry {
+                Some(symmetry) => maze.populate_symmetric(self.algorithm, symmetry, rng),
+                None => maze.populate_with(self.algorithm, rng),
+            }
+
+            // the other generators can't say how far along they are, only when they finish
+            if let Some(on_progress) = on_progress {
+                let total = widen(width) * widen(height);
+                on_progress(total, total);
+            }
+        };
+        let mut frames = match every {
+            Some(every) => Some(maze.record(every, |maze| populate(maze, &mut rng))),
+            None => {
+                populate(&mut maze, &mut rng);
+                None
+            }
+        };
+        if self.solid_border {
+            maze = walled_in(&maze);
+            frames =
+                frames.map(|frames| frames.map(walled_in_tiles, |Coord(x, y)| Coord(x + 1, y + 1)));
+        }
+
+        if self.braid > 0.0 {
+            maze.braid(&mut rng, self.braid);
+        }
+        if self.hardest {
+            maze.place_hardest_start_end();
+        }
+        if self.border_entrances {
+            maze.place_border_entrances(&mut rng);
+        }
+
+        maze.generated_by = Some(Self {
+            seed: Some(seed),
+            ..self.clone()
+        });
+
+        if let Some(frames) = frames.as_mut() {
+            frames.finish(&maze.data);
+        }
+
+        (maze, frames)
+    }
+}
+
+impl MazeBuilder {
+    // the settings as (keyword, text) pairs for a PNG's tEXt chunks, the seed, size and algorithm
+    // always and anything else only when it differs from the default
+    #[cfg(feature = "render")]
+    pub(super) fn png_text(&self) -> Vec<(&'static str, String)> {
+        let mut text = Vec::new();
+        if let Some(seed) = self.seed {
+            text.push(("Seed", seed.to_string()));
+        }
+        text.push(("Size", format!("{}x{}", self.width, self.height)));
+        text.push(("Algorithm", self.algorithm.name().to_string()));
+
+        if self.braid > 0.0 {
+            text.push(("Braid", self.braid.to_string()));
+        }
+        if self.hardest {
+            text.push(("Hardest", "true".to_string()));
+        }
+        if self.wrap {
+            text.push(("Wrap", "true".to_string()));
+        }
+        if let Some(symmetry) = self.symmetry {
+            text.push(("Symmetry", symmetry.name().to_string()));
+        }
+        if self.border_entrances {
+            text.push(("Border entrances", "true".to_string()));
+        }
+        if self.solid_border {
+            text.push(("Solid border", "true".to_string()));
+        }
+        if let Some((x, y)) = self.start_cell {
+            text.push(("Start cell", format!("{},{}", x, y)));
+        }
+
+        text
+    }
+}
+
+// whether a tile is inside the outermost ring of a `width` x `height` maze
+fn is_inside_border(Coord(x, y): Coord, (width, height): (u32, u32)) -> bool {
+    (1..width - 1).contains(&x) && (1..height - 1).contains(&y)
+}
+
+// a maze with a ring of wall around the outside of `inner`, the ring is masked off so nothing
+// which runs afterwards can carve into it
+fn walled_in(inner: &Maze) -> Maze {
+    let (width, height) = (inner.width + 2, inner.height + 2);
+    let mut maze = Maze::from_tiles(width, height, walled_in_tiles(&inner.data));
+    maze.mask = Tiles::init(width, height, false);
+    for tile in row_major(width, height).filter(|&tile| is_inside_border(tile, (width, height))) {
+        if let Some(inside) = maze.mask.at_mut(tile) {
+            *inside = true;
+        }
+    }
+    maze
+}
+
+// the tiles of `inner` with a ring of wall around them
+fn walled_in_tiles(inner: &Tiles<TileState>) -> Tiles<TileState> {
+    let (width, height) = inner.dimensions();
+    let mut tiles = Tiles::new(width + 2, height + 2);
+    for Coord(x, y) in row_major(width, height) {
+        if let (Some(to), Some(&from)) = (tiles.at_mut(Coord(x + 1, y + 1)), inner.at(Coord(x, y)))
+        {
+            *to = from;
+        }
+    }
+    tiles
+}
+
+// the 64 bit FNV-1a parameters
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Turns a string into a seed, so a memorable word can be shared instead of a number.
+///
+/// The string is hashed with 64 bit FNV-1a, which is fixed so a string gives the same seed
+/// and the same maze on every machine and every version.
+///
+/// ```
+/// use labyrinth::{seed_from_str, MazeBuilder};
+///
+/// assert_eq!(seed_from_str("hello"), 0xa430_d846_80aa_bd0b);
+///
+/// let maze = |seed| MazeBuilder::new().width(21).height(21).seed(seed).build();
+/// assert_eq!(maze(seed_from_str("hello")), maze(seed_from_str("hello")));
+/// ```
+pub fn seed_from_str(s: &str) -> u64 {
+    s.bytes().fold(FNV_OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+    })
+}
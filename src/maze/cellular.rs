@@ -0,0 +1,116 @@
+use alloc::{vec, vec::Vec};
+
+use rand::Rng;
+
+use super::{Maze, TileState, Tiles};
+
+// a wall stays a wall with at least this many walls around it, and a passage needs one more
+const WALL_STAYS: usize = 4;
+const WALL_GROWS: usize = 5;
+
+impl Maze {
+    /// Generates an organic cave instead of a maze with a cellular automaton. Every tile starts
+    /// out as a wall with probability `fill_prob`, then for `iterations` steps the tiles are
+    /// smoothed by the 4-5 rule: a wall stays a wall with at least 4 walls in the 8 tiles around
+    /// it, and a passage turns into a wall with at least 5. The outside of the maze counts as wall.
+    ///
+    /// Only the largest connected cave is kept, every smaller pocket is filled back in, so the
+    /// start and end can always reach each other.
+    ///
+    /// ```
+    /// use labyrinth::Maze;
+    /// use rand::{rngs::SmallRng, SeedableRng};
+    ///
+    /// let mut maze = Maze::new(60, 40);
+    /// maze.populate_cellular(&mut SmallRng::seed_from_u64(6), 0.45, 4);
+    ///
+    /// // every passage can be reached from the start
+    /// assert!(!maze.to_ascii_distances().contains('?'));
+    /// assert!(maze.solve_bfs().is_some());
+    /// ```
+    pub fn populate_cellular<R: Rng + ?Sized>(
+        &mut self,
+        rng: &mut R,
+        fill_prob: f64,
+        iterations: usize,
+    ) {
+        let (width, height) = (self.width, self.height);
+
+        let mut walls = Tiles::init(width, height, true);
+        for tile in self.positions() {
+            let wall = rng.gen::<f64>() < fill_prob || self.is_masked(tile);
+            if let Some(wall_tile) = walls.at_mut(tile) {
+                *wall_tile = wall;
+            }
+        }
+
+        for _ in 0..iterations {
+            let mut next = walls.clone();
+            for tile in self.positions() {
+                let around = (-1..=1)
+                    .flat_map(|dy| (-1..=1).map(move |dx| (dx, dy)))
+                    .filter(|&offset| offset != (0, 0))
+                    .filter(|&(dx, dy)| {
+                        let neighbour = tile.offset(dx, dy, width, height, false);
+                        neighbour.is_none_or(|neighbour| walls.at(neighbour) != Some(&false))
+                    })
+                    .count();
+
+                let wall = walls.at(tile) == Some(&true);
+                let threshold = if wall { WALL_STAYS } else { WALL_GROWS };
+                if let Some(next_tile) = next.at_mut(tile) {
+                    *next_tile = around >= threshold || self.is_masked(tile);
+                }
+            }
+            walls = next;
+        }
+
+        for tile in self.positions() {
+            if walls.at(tile) == Some(&false) {
+                self.carve(tile);
+                self.visit(tile);
+            }
+        }
+
+        self.keep_largest_region();
+        self.place_start_end();
+    }
+
+    // fills in every passage which isn't part of the largest connected group of passages
+    fn keep_largest_region(&mut self) {
+        // label each passage with the region it belongs to, flooding out from each unlabelled one
+        let mut regions: Tiles<Option<usize>> = self.blank_tiles();
+        let mut sizes = Vec::new();
+        for tile in self.positions() {
+            if !self.is_passage(tile) || regions.at(tile) != Some(&None) {
+                continue;
+            }
+
+            let region = sizes.len();
+            let mut size = 0;
+            let mut stack = vec![tile];
+            if let Some(label) = regions.at_mut(tile) {
+                *label = Some(region);
+            }
+            while let Some(tile) = stack.pop() {
+                size += 1;
+                for neighbour in self.passage_neighbours(tile) {
+                    if let Some(label @ None) = regions.at_mut(neighbour) {
+                        *label = Some(region);
+                        stack.push(neighbour);
+                    }
+                }
+            }
+            sizes.push(size);
+        }
+
+        let largest = (0..sizes.len()).max_by_key(|&region| sizes[region]);
+        for tile in self.positions() {
+            if let Some(Some(region)) = regions.at(tile) {
+                if Some(*region) != largest {
+                    self.set_tile(tile, TileState::Wall);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use super::{Maze, TileState};
+
+impl Maze {
+    /// The negative of the maze, with every wall turned into a passage and every passage into a
+    /// wall. The start, end and any crossings are left where they are.
+    ///
+    /// The result is a finished maze the same size as this one which wraps if this one does,
+    /// but it is almost never a perfect maze and usually can't be solved. That is the point,
+    /// it is for drawing rather than walking.
+    ///
+    #[cfg_attr(feature = "std", doc = "```")]
+    #[cfg_attr(not(feature = "std"), doc = "```ignore")]
+    /// use labyrinth::{Maze, MazeBuilder, TileState};
+    ///
+    /// let maze = Maze::from_ascii("S #\n# E")?;
+    /// let negative = maze.complement();
+    /// assert_eq!(negative.to_ascii(), "S# \n #E");
+    /// assert_eq!(negative.tile(0, 0), Some(TileState::Start));
+    ///
+    /// // complementing twice gives back the original
+    /// let maze = MazeBuilder::new().width(31).height(17).seed(4).build();
+    /// assert_eq!(maze.complement().complement(), maze);
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn complement(&self) -> Maze {
+        let data = self.data.map(|&tile| match tile {
+            TileState::Wall => TileState::Empty,
+            TileState::Empty => TileState::Wall,
+            other => other,
+        });
+
+        let mut complement = Maze::from_tiles(self.width, self.height, data);
+        complement.set_wrap(self.wrap);
+        complement
+    }
+}
@@ -0,0 +1,94 @@
+use core::{convert::TryFrom, num::TryFromIntError};
+
+use super::Direction;
+
+/// The position of a tile, `x` along the row from the left and `y` down the rows from the top.
+///
+/// The components are the same type as the width and height of a maze, so stepping to the
+/// next tile is checked against the edges rather than relying on `usize` arithmetic not
+/// wrapping around.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Coord(pub u32, pub u32);
+
+impl Coord {
+    /// The tile next to this one in a `width` x `height` maze, `None` if it would be off the edge.
+    ///
+    /// ```
+    /// use labyrinth::{Coord, Direction::*};
+    ///
+    /// // north is towards increasing y, which is down the rows
+    /// let (width, height) = (5, 3);
+    /// let top_left = Coord(0, 0);
+    /// assert_eq!(top_left.neighbour(North, width, height), Some(Coord(0, 1)));
+    /// assert_eq!(top_left.neighbour(East, width, height), Some(Coord(1, 0)));
+    /// assert_eq!(top_left.neighbour(South, width, height), None);
+    /// assert_eq!(top_left.neighbour(West, width, height), None);
+    ///
+    /// let top_right = Coord(4, 0);
+    /// assert_eq!(top_right.neighbour(North, width, height), Some(Coord(4, 1)));
+    /// assert_eq!(top_right.neighbour(East, width, height), None);
+    /// assert_eq!(top_right.neighbour(South, width, height), None);
+    /// assert_eq!(top_right.neighbour(West, width, height), Some(Coord(3, 0)));
+    ///
+    /// let bottom_left = Coord(0, 2);
+    /// assert_eq!(bottom_left.neighbour(North, width, height), None);
+    /// assert_eq!(bottom_left.neighbour(East, width, height), Some(Coord(1, 2)));
+    /// assert_eq!(bottom_left.neighbour(South, width, height), Some(Coord(0, 1)));
+    /// assert_eq!(bottom_left.neighbour(West, width, height), None);
+    ///
+    /// let bottom_right = Coord(4, 2);
+    /// assert_eq!(bottom_right.neighbour(North, width, height), None);
+    /// assert_eq!(bottom_right.neighbour(East, width, height), None);
+    /// assert_eq!(bottom_right.neighbour(South, width, height), Some(Coord(4, 1)));
+    /// assert_eq!(bottom_right.neighbour(West, width, height), Some(Coord(3, 2)));
+    /// ```
+    pub fn neighbour(self, direction: Direction, width: u32, height: u32) -> Option<Coord> {
+        let (dx, dy) = direction.delta();
+        self.offset(dx, dy, width, height, false)
+    }
+
+    // the tile `dx` across and `dy` up, going off an edge either wraps around to the other side
+    // or gives `None`. wrapping all the way back round to the tile itself also gives `None`, so
+    // a tile is never its own neighbour
+    pub(crate) fn offset(
+        self,
+        dx: i32,
+        dy: i32,
+        width: u32,
+        height: u32,
+        wrap: bool,
+    ) -> Option<Coord> {
+        let shift = |v: u32, d: i32, len: u32| {
+            let shifted = if wrap {
+                let len = i64::from(len);
+                u32::try_from((i64::from(v) + i64::from(d)).rem_euclid(len)).ok()
+            } else {
+                v.checked_add_signed(d).filter(|&v| v < len)
+            };
+            shifted.filter(|&shifted| d == 0 || shifted != v)
+        };
+
+        Some(Coord(shift(self.0, dx, width)?, shift(self.1, dy, height)?))
+    }
+}
+
+impl From<Coord> for (usize, usize) {
+    fn from(Coord(x, y): Coord) -> Self {
+        (widen(x), widen(y))
+    }
+}
+
+// a width, height or component of a tile as an index. a u32 always fits in a usize on the 32
+// and 64 bit targets the crate supports, this is the one place one gets widened
+pub(crate) fn widen(n: u32) -> usize {
+    n as usize
+}
+
+impl TryFrom<(usize, usize)> for Coord {
+    type Error = TryFromIntError;
+
+    /// Fails if either component is too big to be a tile of any maze.
+    fn try_from((x, y): (usize, usize)) -> Result<Self, TryFromIntError> {
+        Ok(Coord(u32::try_from(x)?, u32::try_from(y)?))
+    }
+}
@@ -0,0 +1,96 @@
+use std::convert::TryFrom;
+
+use super::{Coord, Maze, TileState, Tiles};
+use anyhow::{bail, Context};
+
+impl Maze {
+    /// Writes the tiles as CSV, one line per row of the maze with the tiles separated by commas.
+    ///
+    /// Each tile is written as a number: 0 for a wall, 1 for a passage, 2 for the start, 3 for
+    /// the end and 4 for a crossing.
+    ///
+    /// ```
+    /// use labyrinth::Maze;
+    ///
+    /// let maze = Maze::from_ascii("S #\n# E").unwrap();
+    /// assert_eq!(maze.to_csv(), "2,1,0\n0,1,3");
+    /// assert_eq!(Maze::from_csv(&maze.to_csv()).unwrap(), maze);
+    /// ```
+    pub fn to_csv(&self) -> String {
+        let (width, height) = (self.width, self.height);
+        let rows: Vec<String> = (0..height)
+            .map(|y| {
+                let codes: Vec<_> = (0..width)
+                    .map(|x| {
+                        let code = match self.data.at(Coord(x, y)) {
+                            Some(TileState::Wall) | None => 0,
+                            Some(TileState::Empty) => 1,
+                            Some(TileState::Start) => 2,
+                            Some(TileState::End) => 3,
+                            Some(TileState::Crossing) => 4,
+                        };
+                        code.to_string()
+                    })
+                    .collect();
+                codes.join(",")
+            })
+            .collect();
+
+        rows.join("\n")
+    }
+
+    /// Loads a maze from CSV like `to_csv` produces.
+    ///
+    /// The size of the maze is read from the text, so every row has to have the same number
+    /// of tiles.
+    pub fn from_csv(s: &str) -> anyhow::Result<Maze> {
+        let rows: Vec<Vec<&str>> = s
+            .lines()
+            .filter(|row| !row.trim().is_empty())
+            .map(|row| row.split(',').map(str::trim).collect())
+            .collect();
+        let width = rows.first().map_or(0, Vec::len);
+        if width == 0 {
+            bail!("Cannot load a maze from CSV without any tiles.");
+        }
+
+        if let Some((y, row)) = rows.iter().enumerate().find(|(_, row)| row.len() != width) {
+            bail!(
+                "Row {} of the maze has {} tiles but the first row has {}.",
+                y,
+                row.len(),
+                width
+            );
+        }
+
+        let dimension = |n: usize| {
+            u32::try_from(n)
+                .with_context(|| format!("The maze is too big at {}x{} tiles.", width, rows.len()))
+        };
+        let (columns, height) = (dimension(width)?, dimension(rows.len())?);
+
+        let mut data = Tiles::new(columns, height);
+        for (row, y) in rows.iter().zip(0..) {
+            for (&code, x) in row.iter().zip(0..) {
+                let tile = match code {
+                    "0" => TileState::Wall,
+                    "1" => TileState::Empty,
+                    "2" => TileState::Start,
+                    "3" => TileState::End,
+                    "4" => TileState::Crossing,
+                    _ => bail!(
+                        "Unknown tile {:?} at ({}, {}) in the maze, expected a number from 0 to 4.",
+                        code,
+                        x,
+                        y
+                    ),
+                };
+                if let Some(t) = data.at_mut(Coord(x, y)) {
+                    *t = tile;
+                }
+            }
+        }
+
+        Ok(Maze::from_tiles(columns, height, data))
+    }
+}
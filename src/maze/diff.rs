@@ -0,0 +1,40 @@
+use super::{Maze, TileState};
+
+impl Maze {
+    /// The tiles where two mazes of the same size disagree, as `(x, y, ours, theirs)` in row
+    /// order. Handy for checking a change to a generator didn't change what it generates.
+    ///
+    /// Returns an error if the mazes are different sizes.
+    ///
+    /// ```
+    /// use labyrinth::{Maze, TileState};
+    ///
+    /// let maze = Maze::from_ascii("S #\n# E").unwrap();
+    /// assert!(maze.diff(&maze).unwrap().is_empty());
+    ///
+    /// let other = Maze::from_ascii("S  \n# E").unwrap();
+    /// let diff = maze.diff(&other).unwrap();
+    /// assert_eq!(diff, [(2, 0, TileState::Wall, TileState::Empty)]);
+    /// ```
+    pub fn diff(&self, other: &Maze) -> anyhow::Result<Vec<(usize, usize, TileState, TileState)>> {
+        if self.dimensions() != other.dimensions() {
+            anyhow::bail!(
+                "Cannot diff a {}x{} maze with a {}x{} maze, they must be the same size.",
+                self.width,
+                self.height,
+                other.width,
+                other.height
+            );
+        }
+
+        Ok(self
+            .positions()
+            .filter_map(|tile| {
+                let ours = self.data.at(tile).copied().unwrap_or_default();
+                let theirs = other.data.at(tile).copied().unwrap_or_default();
+                let (x, y) = tile.into();
+                (ours != theirs).then_some((x, y, ours, theirs))
+            })
+            .collect())
+    }
+}
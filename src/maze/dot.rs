@@ -0,0 +1,115 @@
+use std::{ffi::OsStr, fmt::Write, fs, path::Path};
+
+use anyhow::Context;
+
+use super::{hex_colour, Coord, Maze, TileState};
+
+impl Maze {
+    /// Saves the maze as a GraphViz DOT graph, see `to_dot`.
+    pub fn save_dot<S: AsRef<OsStr> + ?Sized>(&self, s: &S) -> anyhow::Result<()> {
+        let path = Path::new(s);
+        fs::write(path, self.to_dot())
+            .with_context(|| format!("Failed to write the graph to {}.", path.display()))
+    }
+
+    /// Describes the maze as an undirected GraphViz DOT graph, with a node `n_x_y` for every
+    /// passage tile and an edge between every pair of tiles which can be stepped between.
+    ///
+    /// The start and end nodes are filled in their usual colours. Nodes and edges are written
+    /// in row order, so the same maze always gives the same text.
+    ///
+    /// ```
+    /// use labyrinth::{MazeBuilder, TileState};
+    ///
+    /// let maze = MazeBuilder::new().width(15).height(11).seed(2).build();
+    /// assert!(maze.is_perfect());
+    ///
+    /// let passages = (0..11)
+    ///     .flat_map(|y| (0..15).map(move |x| (x, y)))
+    ///     .filter(|&(x, y)| maze.tile(x, y) != Some(TileState::Wall))
+    ///     .count();
+    /// let dot = maze.to_dot();
+    /// // a tree has one edge fewer than it has nodes
+    /// assert_eq!(dot.lines().filter(|line| line.contains(" -- ")).count(), passages - 1);
+    /// assert!(dot.starts_with("graph maze {\n"));
+    /// ```
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("graph maze {\n");
+
+        let passages: Vec<_> = self
+            .positions()
+            .filter(|&tile| self.is_passage(tile))
+            .collect();
+        for &tile in &passages {
+            let Coord(x, y) = tile;
+            match self.data.at(tile) {
+                Some(&tile @ (TileState::Start | TileState::End)) => {
+                    let _ = writeln!(
+                        dot,
+                        r#"    n_{}_{} [style=filled, fillcolor="{}"];"#,
+                        x,
+                        y,
+                        hex_colour(&tile)
+                    );
+                }
+                _ => {
+                    let _ = writeln!(dot, "    n_{}_{};", x, y);
+                }
+            }
+        }
+
+        for ((x, y), (nx, ny)) in self.passages() {
+            let _ = writeln!(dot, "    n_{}_{} -- n_{}_{};", x, y, nx, ny);
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Every pair of passage tiles which can be stepped between, the edges of the maze as a
+    /// graph with the passage tiles as its nodes. Each edge is listed once, from whichever of
+    /// its tiles comes first in row order, and the edges are in row order of that tile.
+    ///
+    /// A perfect maze is a spanning tree of its passages, so it has one edge fewer than it has
+    /// passage tiles.
+    ///
+    /// ```
+    /// use labyrinth::{Maze, MazeBuilder, TileState};
+    ///
+    /// let maze = Maze::from_ascii("S  \n## \nE  ")?;
+    /// let edges = maze.passages();
+    /// assert_eq!(edges[..3], [((0, 0), (1, 0)), ((1, 0), (2, 0)), ((2, 0), (2, 1))]);
+    /// assert_eq!(edges[3..], [((2, 1), (2, 2)), ((0, 2), (1, 2)), ((1, 2), (2, 2))]);
+    ///
+    /// let maze = MazeBuilder::new().width(31).height(17).seed(5).build();
+    /// let open = (0..17)
+    ///     .flat_map(|y| (0..31).map(move |x| (x, y)))
+    ///     .filter(|&(x, y)| maze.tile(x, y) != Some(TileState::Wall))
+    ///     .count();
+    /// assert!(maze.is_perfect());
+    /// assert_eq!(maze.passages().len(), open - 1);
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn passages(&self) -> Vec<((usize, usize), (usize, usize))> {
+        let row_order = |Coord(x, y): Coord| (y, x);
+
+        let mut edges = Vec::new();
+        for tile in self.positions().filter(|&tile| self.is_passage(tile)) {
+            let mut later: Vec<_> = self
+                .passage_neighbours(tile)
+                .into_iter()
+                .filter(|&neighbour| row_order(neighbour) > row_order(tile))
+                .collect();
+            // a maze two tiles across which wraps reaches the same tile going either way round
+            later.sort_by_key(|&neighbour| row_order(neighbour));
+            later.dedup();
+
+            edges.extend(
+                later
+                    .into_iter()
+                    .map(|neighbour| (tile.into(), neighbour.into())),
+            );
+        }
+        edges
+    }
+}
@@ -0,0 +1,98 @@
+use alloc::{vec, vec::Vec};
+
+use rand::{prelude::SliceRandom, Rng};
+
+use super::{coord::widen, Coord, Direction, Maze};
+
+impl Maze {
+    /// Generates the maze one row at a time using Eller's algorithm.
+    ///
+    /// Only the set membership of the current row is kept, every cell in the row is labelled
+    /// with the id of the set it belongs to. Cells in the same set are already connected by
+    /// some path through the rows above, so joining two cells of the same set would make a loop.
+    ///
+    /// The last row joins every set left in it, so nothing is cut off from the rest.
+    ///
+    /// ```
+    /// use labyrinth::Maze;
+    /// use rand::{rngs::SmallRng, SeedableRng};
+    ///
+    /// for seed in 0..8 {
+    ///     for &(width, height) in &[(31, 21), (41, 3), (41, 1), (3, 41)] {
+    ///         let mut maze = Maze::new(width, height);
+    ///         maze.populate_eller(&mut SmallRng::seed_from_u64(seed));
+    ///         assert!(maze.is_perfect());
+    ///     }
+    /// }
+    /// ```
+    pub fn populate_eller<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        let (cols, rows) = self.cell_dimensions();
+
+        // None marks a cell with no passage coming down from the row above
+        let mut sets: Vec<Option<usize>> = vec![None; widen(cols)];
+        let mut next_id = 0;
+
+        for cy in 0..rows {
+            let last_row = cy + 1 == rows;
+
+            // cells not connected to the row above start off in a set of their own
+            for (cx, set) in (0..cols).zip(sets.iter_mut()) {
+                if set.is_none() {
+                    *set = Some(next_id);
+                    next_id += 1;
+                }
+
+                self.carve(Coord(cx * 2, cy * 2));
+                self.visit(Coord(cx * 2, cy * 2));
+            }
+
+            // randomly join neighbouring cells in different sets,
+            // the last row has to join everything left over so the maze is connected
+            for cx in 0..cols.saturating_sub(1) {
+                let (left, right) = (sets[widen(cx)], sets[widen(cx) + 1]);
+                if left != right && (last_row || rng.gen()) {
+                    if let Some((_, wall)) =
+                        self.cell_towards(Coord(cx * 2, cy * 2), Direction::East)
+                    {
+                        self.carve(wall);
+                    }
+
+                    // merge the right set into the left one
+                    for set in sets.iter_mut().filter(|set| **set == right) {
+                        *set = left;
+                    }
+                }
+            }
+
+            if last_row {
+                break;
+            }
+
+            // every set needs at least one passage down so it doesn't get closed off,
+            // the cells which don't get one start the next row in a fresh set
+            let mut members: Vec<(usize, u32)> = (0..cols)
+                .zip(&sets)
+                .filter_map(|(cx, set)| set.map(|id| (id, cx)))
+                .collect();
+            members.shuffle(rng);
+            // stable sort keeps the shuffled order within each set
+            members.sort_by_key(|&(id, _)| id);
+
+            let mut next_row = vec![None; widen(cols)];
+            for (i, &(id, cx)) in members.iter().enumerate() {
+                let first_in_set = i == 0 || members[i - 1].0 != id;
+                if first_in_set || rng.gen() {
+                    let cell = Coord(cx * 2, cy * 2);
+                    if let Some((_, wall)) = self.cell_towards(cell, Direction::North) {
+                        self.carve(wall);
+                    }
+                    next_row[widen(cx)] = Some(id);
+                }
+            }
+
+            sets = next_row;
+        }
+
+        self.place_start_end();
+    }
+}
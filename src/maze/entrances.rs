@@ -0,0 +1,98 @@
+use alloc::vec::Vec;
+
+use rand::{prelude::SliceRandom, Rng};
+
+use super::{Coord, Direction, Maze, TileState};
+
+impl Maze {
+    /// Moves the start onto the top or left edge of the maze and the end onto the bottom or
+    /// right edge, so the maze is entered from outside. Meant to run after the maze is populated.
+    ///
+    /// An entrance is either a passage already on the edge, or a wall on the edge with a
+    /// passage right behind it which gets carved open, so neither entrance is ever a dead
+    /// wall. The end is always reachable from the start. The maze is left unchanged if the
+    /// edges have nowhere to put the entrances.
+    ///
+    /// ```
+    /// use labyrinth::{Maze, TileState};
+    /// use rand::{rngs::SmallRng, SeedableRng};
+    ///
+    /// let mut rng = SmallRng::seed_from_u64(5);
+    /// let mut maze = Maze::new(30, 20);
+    /// maze.populate(&mut rng);
+    /// maze.place_border_entrances(&mut rng);
+    ///
+    /// let find = |state| {
+    ///     (0..20)
+    ///         .flat_map(|y| (0..30).map(move |x| (x, y)))
+    ///         .find(|&(x, y)| maze.tile(x, y) == Some(state))
+    ///         .unwrap()
+    /// };
+    /// let (start, end) = (find(TileState::Start), find(TileState::End));
+    /// assert!(start.0 == 0 || start.1 == 0);
+    /// assert!(end.0 == 29 || end.1 == 19);
+    /// assert!(maze.solve_bfs().is_some());
+    /// ```
+    pub fn place_border_entrances<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        // north steps in from the top edge and east from the left edge
+        let starts = self.entrances(&[Direction::North, Direction::East]);
+        let Some(&(start, inside)) = starts.choose(rng) else {
+            return;
+        };
+
+        let distances = self.distances_from(inside);
+        let ends: Vec<_> = self
+            .entrances(&[Direction::South, Direction::West])
+            .into_iter()
+            .filter(|&(end, inside)| end != start && matches!(distances.at(inside), Some(Some(_))))
+            .collect();
+        let Some(&(end, _)) = ends.choose(rng) else {
+            return;
+        };
+
+        for tile in self.positions() {
+            if matches!(self.data.at(tile), Some(TileState::Start | TileState::End)) {
+                self.carve(tile);
+            }
+        }
+        self.set_tile(start, TileState::Start);
+        self.set_tile(end, TileState::End);
+    }
+
+    // the tiles along the edges which step into the maze in one of the `inwards` directions
+    // that could be opened up as an entrance, paired with the passage they lead into. walls only
+    // count if the passage behind is their only open neighbour, so opening one adds no loops
+    fn entrances(&self, inwards: &[Direction]) -> Vec<(Coord, Coord)> {
+        let (width, height) = (self.width, self.height);
+
+        let mut entrances = Vec::new();
+        for &inward in inwards {
+            let edge: Vec<_> = match inward {
+                Direction::North => (0..width).map(|x| Coord(x, 0)).collect(),
+                Direction::East => (0..height).map(|y| Coord(0, y)).collect(),
+                Direction::South => (0..width).map(|x| Coord(x, height - 1)).collect(),
+                Direction::West => (0..height).map(|y| Coord(width - 1, y)).collect(),
+            };
+
+            for tile in edge {
+                if self.is_passage(tile) {
+                    entrances.push((tile, tile));
+                    continue;
+                }
+
+                let open: Vec<_> = Direction::ALL
+                    .iter()
+                    .filter_map(|&direction| self.leads_to(tile, direction))
+                    .collect();
+                match (open.as_slice(), self.step(tile, inward)) {
+                    ([only], Some(behind)) if *only == behind && !self.is_masked(tile) => {
+                        entrances.push((tile, behind))
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        entrances
+    }
+}
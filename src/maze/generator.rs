@@ -0,0 +1,194 @@
+use alloc::{vec, vec::Vec};
+
+use rand::{prelude::SliceRandom, Rng};
+
+use super::{coord::widen, Coord, Direction, Maze, TileState};
+
+/// What changed in one step of a `MazeGenerator`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct GenStep {
+    /// The tile which was carved into a passage, if any.
+    pub carved: Option<(usize, usize)>,
+    /// Whether the maze is finished, only set on the last step which also places the start
+    /// and end.
+    pub done: bool,
+}
+
+/// Runs the depth first search of `Maze::populate` one step at a time, so generation can be
+/// paused, resumed or interleaved with other work.
+///
+/// Draining the generator leaves the maze exactly as `populate` would with the same RNG.
+///
+/// ```
+/// use labyrinth::{Maze, MazeGenerator};
+/// use rand::{rngs::SmallRng, SeedableRng};
+///
+/// let mut stepped = Maze::new(21, 21);
+/// let mut rng = SmallRng::seed_from_u64(8);
+/// let carved = MazeGenerator::new(&mut stepped, &mut rng)
+///     .filter(|step| step.carved.is_some())
+///     .count();
+/// assert!(carved > 0);
+///
+/// let mut populated = Maze::new(21, 21);
+/// populated.populate(&mut SmallRng::seed_from_u64(8));
+/// assert_eq!(stepped, populated);
+/// ```
+#[derive(Debug)]
+pub struct MazeGenerator<'a, R: Rng + ?Sized> {
+    maze: &'a mut Maze,
+    rng: &'a mut R,
+    // the path back to the start of the search, `None` until the start has been picked
+    stack: Option<Vec<Coord>>,
+    // reused for the neighbours of every tile, so a step doesn't allocate
+    neighbours: [(Coord, Direction); 4],
+    done: bool,
+}
+
+impl<'a, R: Rng + ?Sized> MazeGenerator<'a, R> {
+    /// Starts generating into a maze of solid wall, nothing is carved until the first step.
+    pub fn new(maze: &'a mut Maze, rng: &'a mut R) -> Self {
+        Self {
+            maze,
+            rng,
+            stack: None,
+            neighbours: [(Coord(0, 0), Direction::North); 4],
+            done: false,
+        }
+    }
+
+    // starts the search from a given tile instead of a random one
+    pub(super) fn starting_at(maze: &'a mut Maze, rng: &'a mut R, start: Coord) -> Self {
+        maze.visit(start);
+        Self {
+            stack: Some(vec![start]),
+            ..Self::new(maze, rng)
+        }
+    }
+
+    // drains the generator, calling back with the tiles finished so far and the total tiles
+    // every time another 0.5% of the tiles are finished, and once more with every tile at the
+    // end. a tile is finished once it is carved or it is a wall the search can never carve
+    pub(super) fn finish_with_progress<F: FnMut(usize, usize)>(mut self, mut on_progress: F) {
+        let total = widen(self.maze.width) * widen(self.maze.height);
+        let every = (total / 200).max(1);
+
+        // nothing outside of the mask is ever carved
+        let mut finished = self.maze.mask.map(|&inside| !inside);
+        let mut count = finished.iter().filter(|&&done| done).count();
+        let mut reported = count / every;
+        while let Some(carved) = self.advance() {
+            let Some(tile) = carved else {
+                continue;
+            };
+
+            // carving a tile can hem in the walls around it
+            let maze = &*self.maze;
+            let around = (-1..=1)
+                .flat_map(|dy| (-1..=1).map(move |dx| (dx, dy)))
+                .filter(|&offset| offset != (0, 0))
+                .filter_map(|(dx, dy)| maze.offset(tile, dx, dy));
+            for neighbour in core::iter::once(tile).chain(around) {
+                let done = neighbour == tile || maze.is_hemmed_in(neighbour);
+                if let Some(seen @ false) = finished.at_mut(neighbour) {
+                    if done {
+                        *seen = true;
+                        count += 1;
+                    }
+                }
+            }
+
+            if count / every > reported {
+                reported = count / every;
+                on_progress(count, total);
+            }
+        }
+        if count != total {
+            on_progress(total, total);
+        }
+    }
+
+    // keep picking until the start lands inside the mask, as long as there is an inside
+    fn pick_start(&mut self) -> Coord {
+        let maze = &mut *self.maze;
+        let has_inside = maze.mask.iter().any(|&inside| inside);
+        let start = loop {
+            let start = Coord(
+                self.rng.gen_range(0..maze.width),
+                self.rng.gen_range(0..maze.height),
+            );
+            if !maze.is_masked(start) || !has_inside {
+                break start;
+            }
+        };
+
+        maze.visit(start);
+        start
+    }
+}
+
+impl<'a, R: Rng + ?Sized> Iterator for MazeGenerator<'a, R> {
+    type Item = GenStep;
+
+    fn next(&mut self) -> Option<GenStep> {
+        let carved = self.advance()?;
+        Some(GenStep {
+            carved: carved.map(Into::into),
+            done: self.done,
+        })
+    }
+}
+
+impl<'a, R: Rng + ?Sized> MazeGenerator<'a, R> {
+    // takes one step of the search, giving the tile it carved if any. `None` once it is done
+    fn advance(&mut self) -> Option<Option<Coord>> {
+        if self.done {
+            return None;
+        }
+
+        if self.stack.is_none() {
+            let start = self.pick_start();
+            self.stack = Some(vec![start]);
+        }
+        let (maze, stack) = (&mut *self.maze, self.stack.as_mut()?);
+
+        // a randomized depth first search, the last step places the start and end
+        let Some(&tile) = stack.last() else {
+            maze.place_start_end();
+            self.done = true;
+            return Some(None);
+        };
+
+        // shuffle the neighbours
+        let neighbours = maze.candidate_neighbours(tile, &mut self.neighbours);
+        neighbours.shuffle(self.rng);
+
+        // write to the grid after we have found tiles with no neighbours
+        let mut carved = None;
+        if let Some(&state) = maze.data.at(tile) {
+            if state == TileState::Wall && !maze.is_masked(tile) {
+                carved = Some(tile);
+            }
+
+            // find a neighbour if one exists
+            if let Some((next, _)) = neighbours
+                .iter()
+                .copied()
+                .find(|&(neighbour, d)| maze.is_valid_neighbour(neighbour, d))
+            {
+                maze.carve(tile);
+                maze.visit(next);
+
+                stack.push(next);
+            } else {
+                maze.carve(tile);
+                stack.truncate(stack.len() - 1);
+            }
+        } else {
+            // invalid tile
+            stack.truncate(stack.len() - 1);
+        }
+
+        Some(carved)
+    }
+}
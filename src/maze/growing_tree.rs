@@ -0,0 +1,199 @@
+use alloc::{vec, vec::Vec};
+
+use rand::{prelude::SliceRandom, Rng};
+
+use super::{gen_index, Direction, Maze, Tiles};
+
+/// How the growing tree algorithm picks the next active cell to grow from.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum GrowingTreeSelection {
+    /// The most recently added cell, giving the long winding passages of a depth first search.
+    Newest,
+    /// The least recently added cell, giving long straight passages out from the start.
+    Oldest,
+    /// Any cell at random, giving the short branchy passages of Prim's algorithm.
+    Random,
+    /// The newest cell with the given probability and otherwise a random cell.
+    Mixed(f64),
+}
+
+impl Maze {
+    /// Generates the maze using the growing tree algorithm, which covers everything from a
+    /// depth first search to Prim's algorithm depending on how the next cell is selected.
+    ///
+    /// `Newest` is the depth first search of the classic recursive backtracker carried out
+    /// across the lattice of cells, so it leaves as many dead ends as one. This isn't the tile
+    /// by tile backtracker of `populate`.
+    ///
+    /// ```
+    /// use labyrinth::{GrowingTreeSelection, Maze};
+    /// use rand::{prelude::SliceRandom, rngs::SmallRng, SeedableRng};
+    ///
+    /// // a recursive backtracker over the 21 x 21 cells of a 41 x 41 maze, counting the cells
+    /// // it leaves joined to just one other
+    /// fn backtracker_dead_ends(seed: u64) -> usize {
+    ///     let (cols, rows) = (21, 21);
+    ///     let mut rng = SmallRng::seed_from_u64(seed);
+    ///     let mut visited = vec![false; cols * rows];
+    ///     let mut joins = vec![0; cols * rows];
+    ///     let mut stack = vec![0];
+    ///     visited[0] = true;
+    ///     while let Some(&cell) = stack.last() {
+    ///         let (x, y) = (cell % cols, cell / cols);
+    ///         let mut next = Vec::new();
+    ///         if x > 0 {
+    ///             next.push(cell - 1);
+    ///         }
+    ///         if x + 1 < cols {
+    ///             next.push(cell + 1);
+    ///         }
+    ///         if y > 0 {
+    ///             next.push(cell - cols);
+    ///         }
+    ///         if y + 1 < rows {
+    ///             next.push(cell + cols);
+    ///         }
+    ///         next.retain(|&n| !visited[n]);
+    ///         match next.choose(&mut rng) {
+    ///             Some(&n) => {
+    ///                 visited[n] = true;
+    ///                 joins[cell] += 1;
+    ///                 joins[n] += 1;
+    ///                 stack.push(n);
+    ///             }
+    ///             None => {
+    ///                 stack.pop();
+    ///             }
+    ///         }
+    ///     }
+    ///     joins.iter().filter(|&&n| n == 1).count()
+    /// }
+    ///
+    /// let dead_ends = |seed, selection| {
+    ///     let mut maze = Maze::new(41, 41);
+    ///     maze.populate_growing_tree(&mut SmallRng::seed_from_u64(seed), selection);
+    ///     maze.stats().dead_ends
+    /// };
+    ///
+    /// let seeds = 0..8;
+    /// let backtracker: usize = seeds.clone().map(backtracker_dead_ends).sum();
+    /// let newest: usize = seeds
+    ///     .clone()
+    ///     .map(|seed| dead_ends(seed, GrowingTreeSelection::Newest))
+    ///     .sum();
+    /// let random: usize = seeds
+    ///     .map(|seed| dead_ends(seed, GrowingTreeSelection::Random))
+    ///     .sum();
+    ///
+    /// // within 10% of each other, while picking at random leaves over twice as many
+    /// assert!(newest.abs_diff(backtracker) * 10 < backtracker);
+    /// assert!(random > newest * 2);
+    /// ```
+    pub fn populate_growing_tree<R: Rng + ?Sized>(
+        &mut self,
+        rng: &mut R,
+        selection: GrowingTreeSelection,
+    ) {
+        self.populate_growing_tree_with_straightness(rng, selection, 0.0);
+    }
+
+    /// Generates the maze using the growing tree algorithm, carrying on in the same direction
+    /// as the cell was carved into with probability `straightness` before picking at random.
+    ///
+    /// At `0.0` this is `populate_growing_tree`, and with `GrowingTreeSelection::Newest` the
+    /// ordinary depth first search of the recursive backtracker. Towards `1.0` the passages
+    /// run in long straight lines, only turning when they hit something.
+    ///
+    /// ```
+    /// use labyrinth::{GrowingTreeSelection, Maze, TileState};
+    /// use rand::{rngs::SmallRng, SeedableRng};
+    ///
+    /// // the passage tiles joined to exactly two others which aren't opposite each other
+    /// let turns = |straightness| {
+    ///     let mut maze = Maze::new(61, 61);
+    ///     let mut rng = SmallRng::seed_from_u64(3);
+    ///     let selection = GrowingTreeSelection::Newest;
+    ///     maze.populate_growing_tree_with_straightness(&mut rng, selection, straightness);
+    ///
+    ///     let open = |x: usize, y: usize| maze.tile(x, y) == Some(TileState::Empty);
+    ///     (1..60)
+    ///         .flat_map(|y| (1..60).map(move |x| (x, y)))
+    ///         .filter(|&(x, y)| open(x, y))
+    ///         .filter(|&(x, y)| {
+    ///             let (west, east) = (open(x - 1, y), open(x + 1, y));
+    ///             let (south, north) = (open(x, y - 1), open(x, y + 1));
+    ///             [west, east, south, north].iter().filter(|&&o| o).count() == 2
+    ///                 && !(west && east)
+    ///                 && !(south && north)
+    ///         })
+    ///         .count()
+    /// };
+    /// assert!(turns(0.9) < turns(0.5));
+    /// assert!(turns(0.5) < turns(0.0));
+    /// ```
+    pub fn populate_growing_tree_with_straightness<R: Rng + ?Sized>(
+        &mut self,
+        rng: &mut R,
+        selection: GrowingTreeSelection,
+        straightness: f64,
+    ) {
+        let start = self.random_cell(rng);
+        self.carve(start);
+        self.visit(start);
+
+        // the direction each active cell was carved into, the start wasn't carved into at all
+        let mut heading: Tiles<Option<Direction>> = self.blank_tiles();
+
+        // the cells which might still have unvisited neighbours, oldest first
+        let mut active = vec![start];
+        while !active.is_empty() {
+            let index = match selection {
+                GrowingTreeSelection::Newest => active.len() - 1,
+                GrowingTreeSelection::Oldest => 0,
+                GrowingTreeSelection::Random => gen_index(rng, 0..active.len()),
+                GrowingTreeSelection::Mixed(newest) => {
+                    // a probability outside of 0..=1 just means always / never
+                    if rng.gen::<f64>() < newest {
+                        active.len() - 1
+                    } else {
+                        gen_index(rng, 0..active.len())
+                    }
+                }
+            };
+
+            let cell = active[index];
+            let unvisited: Vec<_> = Direction::ALL
+                .iter()
+                .filter_map(|&direction| {
+                    let (neighbour, wall) = self.cell_towards(cell, direction)?;
+                    Some((direction, neighbour, wall))
+                })
+                .filter(|&(_, neighbour, _)| !self.is_visited(neighbour))
+                .collect();
+
+            // no random number is drawn without any straightness, so the mazes of
+            // populate_growing_tree stay the same for the same seed
+            let ahead = heading.at(cell).copied().flatten().and_then(|heading| {
+                unvisited
+                    .iter()
+                    .find(|&&(direction, _, _)| direction == heading)
+            });
+            let ahead = ahead.filter(|_| straightness > 0.0 && rng.gen::<f64>() < straightness);
+
+            if let Some(&(direction, next, wall)) = ahead.or_else(|| unvisited.choose(rng)) {
+                self.carve(wall);
+                self.carve(next);
+                self.visit(next);
+                if let Some(tile) = heading.at_mut(next) {
+                    *tile = Some(direction);
+                }
+                active.push(next);
+            } else {
+                // remove keeps the cells in the order they were added, which Oldest relies on
+                active.remove(index);
+            }
+        }
+
+        self.place_start_end();
+    }
+}
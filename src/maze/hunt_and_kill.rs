@@ -0,0 +1,83 @@
+use alloc::vec::Vec;
+
+use rand::{prelude::SliceRandom, Rng};
+
+use super::{Coord, Maze};
+
+impl Maze {
+    /// Generates the maze using the hunt-and-kill algorithm.
+    ///
+    /// ```
+    /// use labyrinth::{Maze, TileState};
+    /// use rand::{rngs::SmallRng, SeedableRng};
+    ///
+    /// // the walk starts somewhere along the corridor and runs into one end, so the rest of
+    /// // the cells are only reached by hunting for them
+    /// for seed in 0..8 {
+    ///     let mut maze = Maze::new(40, 1);
+    ///     maze.populate_hunt_and_kill(&mut SmallRng::seed_from_u64(seed));
+    ///
+    ///     // the last column is outside of the 20 cells, as it has no cell beyond it to join
+    ///     let open = |x| maze.tile(x, 0) != Some(TileState::Wall);
+    ///     assert!((0..39).all(open));
+    ///     assert!(!open(39));
+    ///     assert!(maze.is_perfect());
+    /// }
+    /// ```
+    pub fn populate_hunt_and_kill<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        let mut current = Some(self.random_cell(rng));
+        if let Some(start) = current {
+            self.carve(start);
+            self.visit(start);
+        }
+
+        while let Some(cell) = current {
+            // kill: walk randomly into unvisited cells until there aren't any left
+            let unvisited: Vec<_> = self
+                .cell_neighbours(cell)
+                .into_iter()
+                .filter(|&(neighbour, _)| !self.is_visited(neighbour))
+                .collect();
+
+            current = if let Some(&(next, wall)) = unvisited.choose(rng) {
+                self.carve(wall);
+                self.carve(next);
+                self.visit(next);
+                Some(next)
+            } else {
+                self.hunt(rng)
+            };
+        }
+
+        self.place_start_end();
+    }
+
+    // scan for the first unvisited cell next to the visited region and join it on
+    fn hunt<R: Rng + ?Sized>(&mut self, rng: &mut R) -> Option<Coord> {
+        let (cols, rows) = self.cell_dimensions();
+
+        for cy in 0..rows {
+            for cx in 0..cols {
+                let cell = Coord(cx * 2, cy * 2);
+                if self.is_visited(cell) {
+                    continue;
+                }
+
+                let visited: Vec<_> = self
+                    .cell_neighbours(cell)
+                    .into_iter()
+                    .filter(|&(neighbour, _)| self.is_visited(neighbour))
+                    .collect();
+
+                if let Some(&(_, wall)) = visited.choose(rng) {
+                    self.carve(wall);
+                    self.carve(cell);
+                    self.visit(cell);
+                    return Some(cell);
+                }
+            }
+        }
+
+        None
+    }
+}
@@ -0,0 +1,635 @@
+use std::{convert::TryFrom, ffi::OsStr, fs::File, io::Write, path::Path};
+
+use anyhow::Context;
+use grid::Grid;
+use rgb::{ComponentBytes, RGB8, RGBA8};
+
+use super::{
+    coord::widen, create_file, pad, row_major, ColorScheme, Coord, Maze, MazeBuilder, TileState,
+    Tiles,
+};
+
+impl Maze {
+    /// Loads a mask for `with_mask` from a PNG, light pixels are inside the mask
+    /// while dark or transparent pixels are off limits.
+    ///
+    /// ```
+    /// use labyrinth::{ColorScheme, Maze, TileState};
+    ///
+    /// // only the white passages are light enough to be inside the mask
+    /// let maze = Maze::from_ascii("S  #\n## #\n#  E")?;
+    /// let path = std::env::temp_dir().join("labyrinth_mask_from_png.png");
+    /// maze.save_to_file(&path)?;
+    /// let mask = Maze::mask_from_png(&path)?;
+    /// assert_eq!(mask.size(), (4, 3));
+    /// for (x, y) in (0..4).flat_map(|x| (0..3).map(move |y| (x, y))) {
+    ///     assert_eq!(*mask.get(x, y).unwrap(), maze.tile(x, y) == Some(TileState::Empty));
+    /// }
+    ///
+    /// // and transparent pixels are outside of it, however light they are
+    /// maze.save_to_file_transparent(&path, &ColorScheme::default(), 1)?;
+    /// let mask = Maze::mask_from_png(&path)?;
+    /// assert!(mask.iter().all(|&inside| !inside));
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn mask_from_png<S: AsRef<OsStr> + ?Sized>(s: &S) -> anyhow::Result<Grid<bool>> {
+        let (width, height, pixels) = read_png(Path::new(s))?;
+
+        let mut mask = Grid::init(widen(width), widen(height), false);
+        for (Coord(x, y), pixel) in row_major(width, height).zip(pixels) {
+            let luma = (u32::from(pixel.r) + u32::from(pixel.g) + u32::from(pixel.b)) / 3;
+            if let Some(inside) = mask.get_mut(widen(x), widen(y)) {
+                *inside = luma >= 0x80 && pixel.a >= 0x80;
+            }
+        }
+
+        Ok(mask)
+    }
+
+    /// Loads a maze from a PNG saved by `save_to_file`, mapping each pixel back to a tile.
+    ///
+    /// This only works for images drawn one pixel per tile in the default colours, so any
+    /// pixel which isn't black, white, green, red or the grey of a crossing is an error.
+    /// Transparency is ignored.
+    ///
+    /// ```
+    /// use labyrinth::{Algorithm, Maze, MazeBuilder};
+    ///
+    /// let maze = MazeBuilder::new().width(31).height(21).seed(5).build();
+    /// let path = std::env::temp_dir().join("labyrinth_from_png.png");
+    /// maze.save_to_file(&path)?;
+    /// assert_eq!(Maze::from_png(&path)?, maze);
+    ///
+    /// // a weave maze keeps its crossings
+    /// let weave = MazeBuilder::new()
+    ///     .width(31)
+    ///     .height(21)
+    ///     .algorithm(Algorithm::Weave)
+    ///     .seed(8)
+    ///     .build();
+    /// weave.save_to_file(&path)?;
+    /// let loaded = Maze::from_png(&path)?;
+    /// assert!(loaded.to_ascii().contains('+'));
+    /// assert_eq!(loaded, weave);
+    /// assert_eq!(loaded.solve_bfs(), weave.solve_bfs());
+    ///
+    /// // two pixels per tile puts the walls in the wrong places
+    /// maze.save_to_file_scaled(&path, 2)?;
+    /// assert_ne!(Maze::from_png(&path)?, maze);
+    ///
+    /// let heatmap = std::env::temp_dir().join("labyrinth_from_png_heatmap.png");
+    /// maze.save_distance_heatmap(&heatmap)?;
+    /// assert!(Maze::from_png(&heatmap).is_err());
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn from_png<S: AsRef<OsStr> + ?Sized>(s: &S) -> anyhow::Result<Maze> {
+        let path = Path::new(s);
+        let (width, height, pixels) = read_png(path)?;
+
+        let mut data = Tiles::new(width, height);
+        for (tile, pixel) in row_major(width, height).zip(pixels) {
+            let state = TileState::try_from(pixel.rgb()).ok().with_context(|| {
+                format!(
+                    "Unknown colour #{:02x}{:02x}{:02x} at ({}, {}) in {}, \
+                     expected one of the tile colours.",
+                    pixel.r,
+                    pixel.g,
+                    pixel.b,
+                    tile.0,
+                    tile.1,
+                    path.display()
+                )
+            })?;
+            if let Some(t) = data.at_mut(tile) {
+                *t = state;
+            }
+        }
+
+        Ok(Maze::from_tiles(width, height, data))
+    }
+
+    /// Reads back the `(keyword, text)` pairs stored in a PNG's tEXt chunks, in file order.
+    ///
+    /// Mazes from a `MazeBuilder` are saved with the seed, size and algorithm along with any
+    /// other settings which aren't the defaults, so the maze in a shared image can be made again.
+    /// Once any tile has changed since the maze was built the settings would make a different
+    /// maze, so they aren't saved.
+    ///
+    /// ```
+    /// use labyrinth::{Algorithm, Maze, MazeBuilder};
+    ///
+    /// let maze = MazeBuilder::new()
+    ///     .width(31)
+    ///     .height(17)
+    ///     .seed(42)
+    ///     .algorithm(Algorithm::Prim)
+    ///     .build();
+    /// let path = std::env::temp_dir().join("labyrinth_png_text.png");
+    /// maze.save_to_file(&path)?;
+    ///
+    /// let text = Maze::png_text(&path)?;
+    /// let get = |keyword| text.iter().find(|(k, _)| k == keyword).map(|(_, v)| v.as_str());
+    /// assert_eq!(get("Seed"), Some("42"));
+    /// assert_eq!(get("Size"), Some("31x17"));
+    /// assert_eq!(get("Algorithm"), Some("prim"));
+    ///
+    /// let mut moved = maze.clone();
+    /// let (x, y) = moved.solution().unwrap()[1];
+    /// moved.set_end(x, y)?;
+    /// moved.save_to_file(&path)?;
+    /// assert!(Maze::png_text(&path)?.is_empty());
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn png_text<S: AsRef<OsStr> + ?Sized>(s: &S) -> anyhow::Result<Vec<(String, String)>> {
+        let path = Path::new(s);
+        let bytes = std::fs::read(path)
+            .with_context(|| format!("Failed to open the PNG {}.", path.display()))?;
+        let Some(mut chunks) = bytes.strip_prefix(b"\x89PNG\r\n\x1a\n") else {
+            anyhow::bail!("{} is not a PNG.", path.display());
+        };
+
+        // each chunk is its length, a four letter type, the data and then a CRC
+        let mut text = Vec::new();
+        while let [a, b, c, d, rest @ ..] = chunks {
+            let length = widen(u32::from_be_bytes([*a, *b, *c, *d]));
+            let (Some(kind), Some(data)) = (rest.get(..4), rest.get(4..4 + length)) else {
+                anyhow::bail!("The PNG {} is cut off part way through.", path.display());
+            };
+
+            if kind == b"tEXt" {
+                let mut parts = data.splitn(2, |&byte| byte == 0);
+                let latin1 = |bytes: &[u8]| bytes.iter().copied().map(char::from).collect();
+                let keyword = latin1(parts.next().unwrap_or_default());
+                text.push((keyword, latin1(parts.next().unwrap_or_default())));
+            }
+            if kind == b"IEND" {
+                break;
+            }
+
+            chunks = rest.get(8 + length..).unwrap_or_default();
+        }
+
+        Ok(text)
+    }
+
+    pub fn save_to_file<S: AsRef<OsStr> + ?Sized>(&self, s: &S) -> anyhow::Result<()> {
+        self.write_png(create_file(s)?)
+    }
+
+    /// Saves the maze as a PNG with each tile drawn as a `scale` x `scale` block of pixels.
+    pub fn save_to_file_scaled<S: AsRef<OsStr> + ?Sized>(
+        &self,
+        s: &S,
+        scale: u32,
+    ) -> anyhow::Result<()> {
+        self.write_png_scaled(create_file(s)?, scale)
+    }
+
+    /// Writes the maze as a PNG image to any writer, e.g. `stdout` or a `Vec<u8>`.
+    ///
+    /// ```
+    /// use labyrinth::Maze;
+    ///
+    /// let mut png = Vec::new();
+    /// Maze::new(5, 5).write_png(&mut png)?;
+    /// assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn write_png<W: Write>(&self, w: W) -> anyhow::Result<()> {
+        self.write_png_scaled(w, 1)
+    }
+
+    /// Writes the maze as a PNG image with each tile drawn as a `scale` x `scale` block of pixels.
+    pub fn write_png_scaled<W: Write>(&self, w: W, scale: u32) -> anyhow::Result<()> {
+        self.write_png_with_scheme(w, &ColorScheme::default(), scale)
+    }
+
+    /// Saves the maze as a PNG drawn in the colours of `scheme`, see `write_png_with_scheme`.
+    ///
+    /// ```
+    /// use labyrinth::{ColorScheme, MazeBuilder};
+    /// use rgb::RGB8;
+    ///
+    /// let maze = MazeBuilder::new().width(11).height(7).seed(1).build();
+    /// let default = ColorScheme::default();
+    /// let custom = ColorScheme {
+    ///     wall: RGB8::new(0x20, 0x20, 0x60),
+    ///     ..default
+    /// };
+    ///
+    /// let (mut plain, mut coloured) = (Vec::new(), Vec::new());
+    /// maze.write_png_with_scheme(&mut plain, &default, 1)?;
+    /// maze.write_png_with_scheme(&mut coloured, &custom, 1)?;
+    /// assert_ne!(plain, coloured);
+    ///
+    /// let mut again = Vec::new();
+    /// maze.write_png(&mut again)?;
+    /// assert_eq!(plain, again);
+    ///
+    /// let path = std::env::temp_dir().join("labyrinth_scheme.png");
+    /// maze.save_to_file_with_scheme(&path, &custom, 1)?;
+    /// assert_eq!(std::fs::read(&path)?, coloured);
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn save_to_file_with_scheme<S: AsRef<OsStr> + ?Sized>(
+        &self,
+        s: &S,
+        scheme: &ColorScheme,
+        scale: u32,
+    ) -> anyhow::Result<()> {
+        self.write_png_with_scheme(create_file(s)?, scheme, scale)
+    }
+
+    /// Writes the maze as a PNG image drawn in the colours of `scheme`,
+    /// with each tile drawn as a `scale` x `scale` block of pixels.
+    pub fn write_png_with_scheme<W: Write>(
+        &self,
+        w: W,
+        scheme: &ColorScheme,
+        scale: u32,
+    ) -> anyhow::Result<()> {
+        self.write_png_with_margin(w, scheme, scale, 0)
+    }
+
+    /// Saves the maze as a PNG with a margin around it, see `write_png_with_margin`.
+    pub fn save_to_file_with_margin<S: AsRef<OsStr> + ?Sized>(
+        &self,
+        s: &S,
+        scheme: &ColorScheme,
+        scale: u32,
+        margin_px: u32,
+    ) -> anyhow::Result<()> {
+        self.write_png_with_margin(create_file(s)?, scheme, scale, margin_px)
+    }
+
+    /// Writes the maze as a PNG image like `write_png_with_scheme`, surrounded by `margin_px`
+    /// pixels of the passage colour on every side, see `render_with_margin`.
+    pub fn write_png_with_margin<W: Write>(
+        &self,
+        w: W,
+        scheme: &ColorScheme,
+        scale: u32,
+        margin_px: u32,
+    ) -> anyhow::Result<()> {
+        if scale == 0 {
+            anyhow::bail!("Cannot draw the maze at a scale of 0.");
+        }
+
+        let (width, height) = self.scaled_dimensions(scale);
+        let pixels = self.upscale_tiles(&self.render_with_scheme(scheme), scale, scheme.wall);
+        let pixels = pad(&pixels, widen(width), widen(margin_px), scheme.empty);
+        self.encode(
+            w,
+            width + 2 * margin_px,
+            height + 2 * margin_px,
+            png::ColorType::RGB,
+            png::BitDepth::Eight,
+            pixels.as_bytes(),
+        )
+    }
+
+    /// Saves the maze as a single channel grayscale PNG, see `write_grayscale`.
+    pub fn save_grayscale<S: AsRef<OsStr> + ?Sized>(&self, s: &S) -> anyhow::Result<()> {
+        self.write_grayscale(create_file(s)?)
+    }
+
+    /// Writes the maze as a single channel grayscale PNG image, one pixel per tile with black
+    /// walls and white passages. Much smaller than the colour PNG for mazes fed to other tools.
+    pub fn write_grayscale<W: Write>(&self, w: W) -> anyhow::Result<()> {
+        let (width, height) = self.dimensions();
+        self.encode(
+            w,
+            width,
+            height,
+            png::ColorType::Grayscale,
+            png::BitDepth::Eight,
+            &self.render_luma(),
+        )
+    }
+
+    /// Saves the maze as a 1 bit grayscale PNG, see `write_bilevel`.
+    pub fn save_bilevel<S: AsRef<OsStr> + ?Sized>(&self, s: &S) -> anyhow::Result<()> {
+        self.write_bilevel(create_file(s)?)
+    }
+
+    /// Writes the maze as a 1 bit grayscale PNG image, one pixel per tile with black walls and
+    /// white passages, the smallest a maze can get. The start and end are white like any other
+    /// passage.
+    pub fn write_bilevel<W: Write>(&self, w: W) -> anyhow::Result<()> {
+        let (width, height) = self.dimensions();
+        self.encode(
+            w,
+            width,
+            height,
+            png::ColorType::Grayscale,
+            png::BitDepth::One,
+            &self.render_bilevel(),
+        )
+    }
+
+    /// Saves the maze as a PNG with the passages left transparent, see `write_png_transparent`.
+    ///
+    /// ```
+    /// use labyrinth::{ColorScheme, Maze, TileState};
+    ///
+    /// let maze = Maze::from_ascii("S  \n## \nE  ")?;
+    /// let path = std::env::temp_dir().join("labyrinth_transparent.png");
+    /// maze.save_to_file_transparent(&path, &ColorScheme::default(), 2)?;
+    ///
+    /// let decoder = png::Decoder::new(std::fs::File::open(&path)?);
+    /// let (info, mut reader) = decoder.read_info()?;
+    /// assert_eq!(info.color_type, png::ColorType::RGBA);
+    /// let mut bytes = vec![0; info.buffer_size()];
+    /// reader.next_frame(&mut bytes)?;
+    ///
+    /// // every pixel of an empty tile is see through, and nothing else is
+    /// for (i, pixel) in bytes.chunks(4).enumerate() {
+    ///     let (x, y) = (i % 6 / 2, i / 6 / 2);
+    ///     let alpha = if maze.tile(x, y) == Some(TileState::Empty) { 0x00 } else { 0xff };
+    ///     assert_eq!(pixel[3], alpha);
+    /// }
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn save_to_file_transparent<S: AsRef<OsStr> + ?Sized>(
+        &self,
+        s: &S,
+        scheme: &ColorScheme,
+        scale: u32,
+    ) -> anyhow::Result<()> {
+        self.write_png_transparent(create_file(s)?, scheme, scale)
+    }
+
+    /// Writes the maze as an RGBA PNG image drawn in the colours of `scheme`, except for
+    /// the passages which are left fully transparent so the maze can be drawn over other images.
+    pub fn write_png_transparent<W: Write>(
+        &self,
+        w: W,
+        scheme: &ColorScheme,
+        scale: u32,
+    ) -> anyhow::Result<()> {
+        if scale == 0 {
+            anyhow::bail!("Cannot draw the maze at a scale of 0.");
+        }
+
+        let (width, height) = self.scaled_dimensions(scale);
+        let pixels = self.upscale_tiles(
+            &self.render_rgba_with_scheme(scheme, true),
+            scale,
+            scheme.wall.alpha(0xFF),
+        );
+        self.encode(
+            w,
+            width,
+            height,
+            png::ColorType::RGBA,
+            png::BitDepth::Eight,
+            pixels.as_bytes(),
+        )
+    }
+
+    /// Saves the maze with the shortest path from the start to the end drawn on in blue.
+    pub fn save_solution_to_file<S: AsRef<OsStr> + ?Sized>(&self, s: &S) -> anyhow::Result<()> {
+        let solution = self
+            .solution_tiles()
+            .context("Failed to solve the maze, the end is not reachable from the start.")?;
+
+        self.encode(
+            create_file(s)?,
+            self.width,
+            self.height,
+            png::ColorType::RGB,
+            png::BitDepth::Eight,
+            self.render_rgb(Some(&solution)).as_bytes(),
+        )
+    }
+
+    /// Saves the maze with its dead ends highlighted, see `render_dead_ends`.
+    pub fn save_dead_ends<S: AsRef<OsStr> + ?Sized>(&self, s: &S) -> anyhow::Result<()> {
+        self.encode(
+            create_file(s)?,
+            self.width,
+            self.height,
+            png::ColorType::RGB,
+            png::BitDepth::Eight,
+            self.render_dead_ends().as_bytes(),
+        )
+    }
+
+    /// Saves the maze coloured by how far each tile is from the start,
+    /// going from blue at the start to red at the furthest tile.
+    ///
+    /// ```
+    /// use labyrinth::Maze;
+    ///
+    /// let maze = Maze::from_ascii("S  \n## \nE  \n###\n  #")?;
+    /// let path = std::env::temp_dir().join("labyrinth_heatmap.png");
+    /// maze.save_distance_heatmap(&path)?;
+    ///
+    /// let decoder = png::Decoder::new(std::fs::File::open(&path)?);
+    /// let (info, mut reader) = decoder.read_info()?;
+    /// let mut bytes = vec![0; info.buffer_size()];
+    /// reader.next_frame(&mut bytes)?;
+    /// let pixel = |x: usize, y: usize| {
+    ///     let at = y * info.line_size + x * 3;
+    ///     [bytes[at], bytes[at + 1], bytes[at + 2]]
+    /// };
+    ///
+    /// assert_eq!(pixel(0, 0), [0x00, 0x00, 0xff]);
+    /// // the end is the furthest tile from the start, 6 steps away
+    /// assert_eq!(pixel(0, 2), [0xff, 0x00, 0x00]);
+    /// // and halfway there is halfway between blue and red
+    /// assert_eq!(pixel(2, 1), [0x7f, 0x00, 0x80]);
+    /// // walls and passages which can't be reached keep their usual colours
+    /// assert_eq!(pixel(0, 1), [0x00, 0x00, 0x00]);
+    /// assert_eq!(pixel(0, 4), [0xff, 0xff, 0xff]);
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn save_distance_heatmap<S: AsRef<OsStr> + ?Sized>(&self, s: &S) -> anyhow::Result<()> {
+        let start = self
+            .start
+            .context("Failed to draw the heatmap, the maze has no start.")?;
+
+        self.encode(
+            create_file(s)?,
+            self.width,
+            self.height,
+            png::ColorType::RGB,
+            png::BitDepth::Eight,
+            self.render_heatmap(start).as_bytes(),
+        )
+    }
+
+    /// Saves the distance of every tile from the start as a 16 bit greyscale PNG, one pixel per
+    /// tile, so the exact distances can be read back rather than guessed from a colour.
+    ///
+    /// The start is 0 and each step along a passage adds 1, stopping at 65534. Walls and any
+    /// passages which can't be reached from the start are 65535.
+    ///
+    /// ```
+    /// use labyrinth::Maze;
+    ///
+    /// let maze = Maze::from_ascii("S  \n## \nE  \n###\n  #")?;
+    /// let path = std::env::temp_dir().join("labyrinth_distances.png");
+    /// maze.save_distance_png16(&path)?;
+    ///
+    /// // the decoder cuts samples down to 8 bits unless it's told not to
+    /// let mut decoder = png::Decoder::new(std::fs::File::open(&path)?);
+    /// decoder.set_transformations(png::Transformations::IDENTITY);
+    /// let (info, mut reader) = decoder.read_info()?;
+    /// assert_eq!(info.bit_depth, png::BitDepth::Sixteen);
+    /// let mut bytes = vec![0; info.buffer_size()];
+    /// reader.next_frame(&mut bytes)?;
+    ///
+    /// let distance = |x: usize, y: usize| {
+    ///     let at = y * info.line_size + x * 2;
+    ///     u16::from_be_bytes([bytes[at], bytes[at + 1]])
+    /// };
+    /// assert_eq!(distance(0, 0), 0);
+    /// assert_eq!(distance(2, 1), 3);
+    /// assert_eq!(distance(0, 2), 6);
+    /// assert_eq!(distance(0, 1), u16::MAX);
+    /// // walled off from the start
+    /// assert_eq!(distance(0, 4), u16::MAX);
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn save_distance_png16<S: AsRef<OsStr> + ?Sized>(&self, s: &S) -> anyhow::Result<()> {
+        let start = self
+            .start
+            .context("Failed to save the distances, the maze has no start.")?;
+
+        let distances = self.distances_from(start);
+        let data: Vec<u8> = self
+            .positions()
+            .map(|tile| match distances.at(tile).copied().flatten() {
+                Some(distance) => distance.min(usize::from(u16::MAX - 1)) as u16,
+                None => u16::MAX,
+            })
+            // PNG keeps its 16 bit samples most significant byte first
+            .flat_map(u16::to_be_bytes)
+            .collect();
+
+        self.encode(
+            create_file(s)?,
+            self.width,
+            self.height,
+            png::ColorType::Grayscale,
+            png::BitDepth::Sixteen,
+            &data,
+        )
+    }
+
+    // encode_png_with_depth with the settings of the builder which made the maze, if one did,
+    // written into tEXt chunks so the image can be generated again
+    fn encode<W: Write>(
+        &self,
+        w: W,
+        width: u32,
+        height: u32,
+        colour: png::ColorType,
+        depth: png::BitDepth,
+        data: &[u8],
+    ) -> anyhow::Result<()> {
+        let text = self
+            .generated_by
+            .as_ref()
+            .map_or_else(Vec::new, MazeBuilder::png_text);
+        encode_png_with_depth(w, width, height, colour, depth, &text, data)
+    }
+
+    // anything unreachable keeps its usual colour, so the walls stay black
+    fn render_heatmap(&self, start: Coord) -> Vec<RGB8> {
+        let distances = self.distances_from(start);
+        let max = distances.iter().flatten().copied().max().unwrap_or(0);
+
+        self.positions()
+            .map(|tile| match distances.at(tile).copied().flatten() {
+                Some(distance) => heat_colour(distance, max),
+                None => (&self.data.at(tile).copied().unwrap_or_default()).into(),
+            })
+            .collect()
+    }
+}
+
+pub(crate) fn encode_png<W: Write>(
+    w: W,
+    width: u32,
+    height: u32,
+    colour: png::ColorType,
+    data: &[u8],
+) -> anyhow::Result<()> {
+    encode_png_with_depth(w, width, height, colour, png::BitDepth::Eight, &[], data)
+}
+
+// like encode_png for pixels with fewer or more bits than a byte per channel, and with
+// (keyword, text) pairs to store in tEXt chunks
+fn encode_png_with_depth<W: Write>(
+    w: W,
+    width: u32,
+    height: u32,
+    colour: png::ColorType,
+    depth: png::BitDepth,
+    text: &[(&str, String)],
+    data: &[u8],
+) -> anyhow::Result<()> {
+    let mut encoder = png::Encoder::new(w, width, height);
+    encoder.set_color(colour);
+    encoder.set_depth(depth);
+    let mut writer = encoder
+        .write_header()
+        .context("Failed to write the header of the PNG.")?;
+
+    // the keyword and text are only separated by a null byte
+    for (keyword, value) in text {
+        let chunk = [keyword.as_bytes(), &[0], value.as_bytes()].concat();
+        writer
+            .write_chunk(*b"tEXt", &chunk)
+            .context("Failed to write the metadata of the PNG.")?;
+    }
+
+    writer
+        .write_image_data(data)
+        .context("Failed to write out the image data of the maze.")?;
+
+    Ok(())
+}
+
+// decodes a PNG of any colour type into RGBA pixels in row order
+fn read_png(path: &Path) -> anyhow::Result<(u32, u32, Vec<RGBA8>)> {
+    let file =
+        File::open(path).with_context(|| format!("Failed to open the PNG {}.", path.display()))?;
+
+    // expand everything out to 8 bit samples so only four layouts need handling
+    let mut decoder = png::Decoder::new(file);
+    decoder.set_transformations(png::Transformations::EXPAND | png::Transformations::STRIP_16);
+    let (info, mut reader) = decoder
+        .read_info()
+        .with_context(|| format!("Failed to read the header of the PNG {}.", path.display()))?;
+
+    let mut bytes = vec![0; info.buffer_size()];
+    reader
+        .next_frame(&mut bytes)
+        .with_context(|| format!("Failed to decode the PNG {}.", path.display()))?;
+
+    let (width, height) = (widen(info.width), widen(info.height));
+    let samples = info.color_type.samples();
+    let pixels = bytes
+        .chunks(info.line_size)
+        .take(height)
+        .flat_map(|row| row.chunks(samples).take(width))
+        .map(|pixel| match *pixel {
+            [grey] => RGBA8::new(grey, grey, grey, 0xFF),
+            [grey, alpha] => RGBA8::new(grey, grey, grey, alpha),
+            [r, g, b] => RGBA8::new(r, g, b, 0xFF),
+            [r, g, b, alpha] => RGBA8::new(r, g, b, alpha),
+            _ => RGBA8::new(0, 0, 0, 0),
+        })
+        .collect();
+
+    Ok((info.width, info.height, pixels))
+}
+
+// linearly blend from blue at distance 0 to red at the max distance
+fn heat_colour(distance: usize, max: usize) -> RGB8 {
+    let red = (distance * 0xFF).checked_div(max).unwrap_or(0) as u8;
+    RGB8::new(red, 0x00_u8, 0xFF_u8 - red)
+}
@@ -0,0 +1,49 @@
+use std::{convert::TryFrom, ffi::OsStr, io::Write};
+
+use anyhow::Context;
+
+use super::{create_file, ColorScheme, Maze};
+
+// what `save` uses for .jpg files, high enough that the walls stay mostly crisp
+pub(super) const DEFAULT_JPEG_QUALITY: u8 = 90;
+
+impl Maze {
+    /// Saves the maze as a JPEG at a `quality` from 1 to 100, see `write_jpeg`.
+    pub fn save_jpeg<S: AsRef<OsStr> + ?Sized>(&self, s: &S, quality: u8) -> anyhow::Result<()> {
+        self.write_jpeg(create_file(s)?, quality)
+    }
+
+    /// Writes the maze as a JPEG image to any writer, one pixel per tile in the default
+    /// colours at a `quality` from 1 to 100. Values outside of that range are clamped.
+    ///
+    /// JPEG is lossy so the edges of the walls get smudged, and with one pixel per tile the
+    /// maze can come out too fuzzy to follow. PNG is the better choice for 1px mazes, JPEG is
+    /// for thumbnails of larger ones where the file size matters more.
+    ///
+    /// ```
+    /// use labyrinth::MazeBuilder;
+    ///
+    /// let maze = MazeBuilder::new().width(31).height(17).seed(3).build();
+    /// let mut jpeg = Vec::new();
+    /// maze.write_jpeg(&mut jpeg, 75)?;
+    ///
+    /// // every JPEG starts with the start of image marker and finishes with the end of image
+    /// assert_eq!(jpeg[..2], [0xFF, 0xD8]);
+    /// assert_eq!(jpeg[jpeg.len() - 2..], [0xFF, 0xD9]);
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn write_jpeg<W: Write>(&self, w: W, quality: u8) -> anyhow::Result<()> {
+        let (Ok(width), Ok(height)) = (u16::try_from(self.width), u16::try_from(self.height))
+        else {
+            anyhow::bail!(
+                "Cannot save a {}x{} maze as a JPEG, JPEGs are at most 65535 pixels across.",
+                self.width,
+                self.height
+            );
+        };
+
+        let pixels = self.render_with_scheme(&ColorScheme::default());
+        crate::jpeg::write(w, width, height, &pixels, quality)
+            .context("Failed to write out the JPEG.")
+    }
+}
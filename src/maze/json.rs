@@ -0,0 +1,108 @@
+use std::{collections::BTreeMap, convert::TryFrom};
+
+use super::{coord::widen, Coord, Maze, Tiles};
+use crate::json::{self, Value};
+use anyhow::{bail, Context};
+
+impl Maze {
+    /// Serialises the maze to JSON, with the tiles stored as rows of `to_ascii` characters.
+    ///
+    /// ```json
+    /// {"height":3,"tiles":["S #","# #","  E"],"width":3}
+    /// ```
+    pub fn to_json(&self) -> String {
+        let tiles = self
+            .to_ascii()
+            .lines()
+            .map(|row| Value::String(row.to_owned()))
+            .collect();
+
+        let mut fields = BTreeMap::new();
+        fields.insert("width".to_owned(), Value::Number(self.width.into()));
+        fields.insert("height".to_owned(), Value::Number(self.height.into()));
+        fields.insert("tiles".to_owned(), Value::Array(tiles));
+
+        Value::Object(fields).to_string()
+    }
+
+    /// Loads a maze saved by `to_json`.
+    ///
+    /// ```
+    /// use labyrinth::{Algorithm, Maze, MazeBuilder};
+    ///
+    /// let maze = MazeBuilder::new()
+    ///     .width(21)
+    ///     .height(11)
+    ///     .algorithm(Algorithm::Weave)
+    ///     .seed(4)
+    ///     .build();
+    /// assert_eq!(Maze::from_json(&maze.to_json())?, maze);
+    ///
+    /// // the tiles can be escaped, but not with half of a surrogate pair
+    /// let escaped = r#"{"width":3,"height":1,"tiles":["\u0053 \u0045"]}"#;
+    /// assert_eq!(Maze::from_json(escaped)?, Maze::from_ascii("S E")?);
+    /// for unpaired in [r#"\ud800\u0053"#, r#"\ud800 "#, r#"\udc00"#] {
+    ///     let json = format!(r#"{{"width":1,"height":1,"tiles":["{}"]}}"#, unpaired);
+    ///     let error = Maze::from_json(&json).unwrap_err();
+    ///     assert!(format!("{:#}", error).contains("escape"));
+    /// }
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn from_json(s: &str) -> anyhow::Result<Maze> {
+        let value = json::parse(s).context("Failed to parse the maze JSON.")?;
+
+        let dimension = |key: &str| -> anyhow::Result<u32> {
+            let n = value
+                .get(key)
+                .and_then(Value::as_u64)
+                .with_context(|| format!("The maze JSON has no valid {:?} field.", key))?;
+            u32::try_from(n)
+                .ok()
+                .filter(|&n| n > 0)
+                .with_context(|| format!("The maze {} must be between 1 and {}.", key, u32::MAX))
+        };
+        let width = dimension("width")?;
+        let height = dimension("height")?;
+
+        let rows = value
+            .get("tiles")
+            .and_then(Value::as_array)
+            .context("The maze JSON has no valid \"tiles\" field.")?;
+        if rows.len() != widen(height) {
+            bail!(
+                "The maze JSON has {} rows of tiles but a height of {}.",
+                rows.len(),
+                height
+            );
+        }
+
+        let mut data = Tiles::new(width, height);
+        for (row, y) in rows.iter().zip(0..) {
+            let row = row
+                .as_str()
+                .with_context(|| format!("Row {} of the maze JSON is not a string.", y))?;
+            if row.chars().count() != widen(width) {
+                bail!(
+                    "Row {} of the maze JSON is {} tiles long but the width is {}.",
+                    y,
+                    row.chars().count(),
+                    width
+                );
+            }
+
+            for (glyph, x) in row.chars().zip(0..) {
+                let tile = Maze::tile_from_glyph(glyph).with_context(|| {
+                    format!(
+                        "Unknown tile {:?} at ({}, {}) in the maze JSON.",
+                        glyph, x, y
+                    )
+                })?;
+                if let Some(t) = data.at_mut(Coord(x, y)) {
+                    *t = tile;
+                }
+            }
+        }
+
+        Ok(Maze::from_tiles(width, height, data))
+    }
+}
@@ -0,0 +1,68 @@
+use alloc::vec::Vec;
+
+use rand::{prelude::SliceRandom, Rng};
+
+use super::{coord::widen, Coord, Maze};
+use crate::union_find::UnionFind;
+
+impl Maze {
+    /// Generates the maze using randomized Kruskal's algorithm.
+    ///
+    /// ```
+    /// use labyrinth::{Maze, TileState};
+    /// use rand::{rngs::SmallRng, SeedableRng};
+    ///
+    /// let mut maze = Maze::new(31, 21);
+    /// maze.populate_kruskal(&mut SmallRng::seed_from_u64(2));
+    ///
+    /// // the cells are the tiles with even coordinates, and the walls between them have one
+    /// // odd coordinate
+    /// let open = |x, y| maze.tile(x, y) != Some(TileState::Wall);
+    /// let tiles: Vec<(usize, usize)> = (0..21)
+    ///     .flat_map(|y| (0..31).map(move |x| (x, y)))
+    ///     .collect();
+    /// let cells = tiles.iter().filter(|&&(x, y)| x % 2 == 0 && y % 2 == 0).count();
+    /// let joins = tiles
+    ///     .iter()
+    ///     .filter(|&&(x, y)| (x + y) % 2 == 1 && open(x, y))
+    ///     .count();
+    ///
+    /// // a spanning tree of the cells takes down one wall fewer than there are cells
+    /// assert_eq!(cells, 16 * 11);
+    /// assert_eq!(joins, cells - 1);
+    /// assert!(maze.is_perfect());
+    /// ```
+    pub fn populate_kruskal<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        let (cols, rows) = self.cell_dimensions();
+        let index = |Coord(x, y): Coord| widen(y / 2) * widen(cols) + widen(x / 2);
+
+        // every cell starts out carved as its own set
+        let mut walls = Vec::new();
+        for cy in 0..rows {
+            for cx in 0..cols {
+                let cell = Coord(cx * 2, cy * 2);
+                self.carve(cell);
+                self.visit(cell);
+
+                // only take the walls going up and right so each one appears once
+                walls.extend(
+                    self.cell_neighbours(cell)
+                        .into_iter()
+                        .filter(|&(Coord(nx, ny), _)| nx > cell.0 || ny > cell.1)
+                        .map(|(neighbour, wall)| (cell, wall, neighbour)),
+                );
+            }
+        }
+
+        walls.shuffle(rng);
+
+        let mut sets = UnionFind::new(widen(cols) * widen(rows));
+        for (cell, wall, neighbour) in walls {
+            if sets.union(index(cell), index(neighbour)) {
+                self.carve(wall);
+            }
+        }
+
+        self.place_start_end();
+    }
+}
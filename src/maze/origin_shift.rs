@@ -0,0 +1,187 @@
+use alloc::{collections::VecDeque, vec};
+
+use rand::{prelude::SliceRandom, Rng};
+
+use super::{Coord, Direction, Maze, TileState, Tiles};
+
+// the maze as a tree of cells which all lead to the origin, used by origin_shift_step
+#[derive(Debug, Clone)]
+pub(super) struct OriginShift {
+    origin: Coord,
+    // the next cell along from each cell towards the origin, indexed by cell rather than tile.
+    // the origin is the only cell without one
+    parents: Tiles<Option<Coord>>,
+}
+
+impl Maze {
+    /// Changes the maze a little while keeping it perfect, for mazes which shift about as you
+    /// watch them. Call it over and over, every step changes at most two walls.
+    ///
+    /// The maze is treated as a tree of cells where every cell points at the next one along
+    /// towards an origin cell. Each step points the origin at a random neighbouring cell and
+    /// makes that neighbour the new origin, taking out the wall between them and putting back
+    /// the wall the neighbour used to lead through.
+    ///
+    /// The tree is built from the maze on the first step, walling off any loops. A maze which
+    /// isn't made of cells joined up like the lattice based algorithms make them, such as one
+    /// from `populate`, is first redrawn as rows of cells leading east into a column leading
+    /// north. Changing the maze in any other way in between steps builds the tree again.
+    ///
+    /// ```
+    /// use labyrinth::{Algorithm, MazeBuilder};
+    /// use rand::{rngs::SmallRng, SeedableRng};
+    ///
+    /// let mut maze = MazeBuilder::new()
+    ///     .width(21)
+    ///     .height(21)
+    ///     .algorithm(Algorithm::Prim)
+    ///     .seed(1)
+    ///     .build();
+    /// let mut rng = SmallRng::seed_from_u64(2);
+    /// for _ in 0..1000 {
+    ///     maze.origin_shift_step(&mut rng);
+    /// }
+    /// assert!(maze.is_perfect());
+    ///
+    /// // braiding adds loops, the next step walls them off again
+    /// maze.braid(&mut rng, 1.0);
+    /// assert!(!maze.is_perfect());
+    /// maze.origin_shift_step(&mut rng);
+    /// assert!(maze.is_perfect());
+    /// ```
+    pub fn origin_shift_step<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        let mut shift = match self.origin_shift.take() {
+            Some(shift) => shift,
+            None => self.origin_shift_tree(),
+        };
+
+        let origin = shift.origin;
+        if let Some(&(next, wall)) = self.cell_neighbours(origin).choose(rng) {
+            // the new origin doesn't lead anywhere, so the wall it led through goes back up
+            if let Some(parent) = parent_of(&shift.parents, next) {
+                self.set_tile(wall_between(next, parent), TileState::Wall);
+            }
+            set_parent(&mut shift.parents, origin, Some(next));
+            set_parent(&mut shift.parents, next, None);
+            self.carve(wall);
+            shift.origin = next;
+        }
+
+        self.origin_shift = Some(shift);
+    }
+
+    // finds the tree of the lattice's passages leading to the first cell, or redraws the maze
+    // as a simple tree if any cell can't be reached. only the walls on the tree are left open
+    fn origin_shift_tree(&mut self) -> OriginShift {
+        let (cols, rows) = self.cell_dimensions();
+        let origin = Coord(0, 0);
+        let mut parents = Tiles::init(cols, rows, None);
+
+        // a breadth first search from the origin, marking where each cell was reached from
+        let mut reached = Tiles::init(cols, rows, false);
+        if let Some(cell) = reached.at_mut(Coord(0, 0)) {
+            *cell = self.is_passage(origin);
+        }
+        let mut queue = VecDeque::from(vec![origin]);
+        while let Some(cell) = queue.pop_front() {
+            for &direction in Direction::ALL.iter() {
+                let Some((next, _)) = self.cell_towards(cell, direction) else {
+                    continue;
+                };
+                if let Some(seen @ false) = reached.at_mut(cell_of(next)) {
+                    if self.is_joined(cell, direction) {
+                        *seen = true;
+                        set_parent(&mut parents, next, Some(cell));
+                        queue.push_back(next);
+                    }
+                }
+            }
+        }
+
+        let mut shift = OriginShift { origin, parents };
+        if reached.iter().any(|&seen| !seen) {
+            // every row leads east to the last column, which leads north to the origin
+            let (last_col, last_row) = ((cols - 1) * 2, (rows - 1) * 2);
+            shift.origin = Coord(last_col, last_row);
+            for cy in 0..rows {
+                for cx in 0..cols {
+                    let cell = Coord(cx * 2, cy * 2);
+                    let parent = if cell.0 < last_col {
+                        Some(Coord(cell.0 + 2, cell.1))
+                    } else if cell.1 < last_row {
+                        Some(Coord(cell.0, cell.1 + 2))
+                    } else {
+                        None
+                    };
+                    set_parent(&mut shift.parents, cell, parent);
+                }
+            }
+        }
+
+        // redraw the maze so the only passages are the cells and the walls on the tree
+        let (width, height) = (self.width, self.height);
+        let mut lost_start_end = false;
+        for tile in self.positions() {
+            let Coord(x, y) = tile;
+            let between = match (x % 2, y % 2) {
+                (1, 0) if x + 1 < width => Some((Coord(x - 1, y), Coord(x + 1, y))),
+                (0, 1) if y + 1 < height => Some((Coord(x, y - 1), Coord(x, y + 1))),
+                _ => None,
+            };
+            let is_cell = x % 2 == 0 && y % 2 == 0;
+            let open = match between {
+                Some((a, b)) => {
+                    parent_of(&shift.parents, a) == Some(b)
+                        || parent_of(&shift.parents, b) == Some(a)
+                }
+                None => is_cell,
+            };
+
+            // walls can be put back later, so the start and end have to be on cells
+            if matches!(self.data.at(tile), Some(TileState::Start | TileState::End)) {
+                if is_cell {
+                    continue;
+                }
+                lost_start_end = true;
+            }
+
+            if open {
+                self.carve(tile);
+            } else {
+                self.set_tile(tile, TileState::Wall);
+            }
+        }
+
+        // the start or end was off the cells, so put both back at opposite ends of the maze
+        if lost_start_end {
+            for tile in self.positions() {
+                if matches!(self.data.at(tile), Some(TileState::Start | TileState::End)) {
+                    self.carve(tile);
+                }
+            }
+            self.place_start_end();
+        }
+
+        shift
+    }
+}
+
+// the cell of `parents` a tile of the lattice is stored at
+fn cell_of(Coord(x, y): Coord) -> Coord {
+    Coord(x / 2, y / 2)
+}
+
+fn parent_of(parents: &Tiles<Option<Coord>>, tile: Coord) -> Option<Coord> {
+    parents.at(cell_of(tile)).copied().flatten()
+}
+
+fn set_parent(parents: &mut Tiles<Option<Coord>>, tile: Coord, parent: Option<Coord>) {
+    if let Some(p) = parents.at_mut(cell_of(tile)) {
+        *p = parent;
+    }
+}
+
+// the wall tile between two neighbouring cells
+fn wall_between(a: Coord, b: Coord) -> Coord {
+    Coord((a.0 + b.0) / 2, (a.1 + b.1) / 2)
+}
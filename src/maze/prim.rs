@@ -0,0 +1,60 @@
+use rand::Rng;
+
+use super::{gen_index, Maze};
+
+impl Maze {
+    /// Generates the maze using randomized Prim's algorithm.
+    ///
+    /// ```
+    /// use labyrinth::Maze;
+    /// use rand::{rngs::SmallRng, SeedableRng};
+    ///
+    /// let generate = |seed| {
+    ///     let mut maze = Maze::new(31, 21);
+    ///     maze.populate_prim(&mut SmallRng::seed_from_u64(seed));
+    ///     maze
+    /// };
+    ///
+    /// // the same seed always grows the same maze
+    /// let maze = generate(6);
+    /// assert_eq!(maze, generate(6));
+    /// assert_ne!(maze, generate(7));
+    ///
+    /// assert!(maze.start().is_some());
+    /// assert!(maze.end().is_some());
+    /// assert!(maze.is_perfect());
+    /// assert!(maze.solve_bfs().is_some());
+    /// ```
+    pub fn populate_prim<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        let start = self.random_cell(rng);
+        self.carve(start);
+        self.visit(start);
+
+        // the frontier holds every wall next to the visited region,
+        // paired with the cell on the other side of it
+        let mut frontier = self.cell_neighbours(start);
+
+        while !frontier.is_empty() {
+            // swap_remove keeps picking a wall O(1) and the order only depends on the rng
+            let (cell, wall) = frontier.swap_remove(gen_index(rng, 0..frontier.len()));
+
+            // the inside of the wall is always visited, so only carve
+            // if the cell on the far side is not part of the maze yet
+            if self.is_visited(cell) {
+                continue;
+            }
+
+            self.carve(wall);
+            self.carve(cell);
+            self.visit(cell);
+
+            frontier.extend(
+                self.cell_neighbours(cell)
+                    .into_iter()
+                    .filter(|&(neighbour, _)| !self.is_visited(neighbour)),
+            );
+        }
+
+        self.place_start_end();
+    }
+}
@@ -0,0 +1,90 @@
+use alloc::vec;
+use core::cmp::Ordering;
+
+use rand::Rng;
+
+use super::{gen_coord, Coord, Maze, TileState};
+
+impl Maze {
+    /// Generates the maze by recursively dividing an open chamber with walls.
+    ///
+    /// Every wall which divides a chamber has exactly one gap in it, which is what leaves a
+    /// perfect maze: no gap would cut the two halves off and a second one would make a loop.
+    ///
+    /// ```
+    /// use labyrinth::{Maze, TileState};
+    /// use rand::{rngs::SmallRng, SeedableRng};
+    ///
+    /// for seed in 0..8 {
+    ///     let mut maze = Maze::new(31, 21);
+    ///     maze.populate_recursive_division(&mut SmallRng::seed_from_u64(seed));
+    ///     assert!(maze.is_perfect());
+    ///
+    ///     // the first wall runs right across the maze, through the tiles between the cells
+    ///     let open = |x, y| maze.tile(x, y) != Some(TileState::Wall);
+    ///     let gaps_down = |x| (0..21).step_by(2).filter(|&y| open(x, y)).count();
+    ///     let gaps_across = |y| (0..31).step_by(2).filter(|&x| open(x, y)).count();
+    ///     let first_wall = (1..31).step_by(2).any(|x| gaps_down(x) == 1)
+    ///         || (1..21).step_by(2).any(|y| gaps_across(y) == 1);
+    ///     assert!(first_wall);
+    /// }
+    /// ```
+    pub fn populate_recursive_division<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        let (cols, rows) = self.cell_dimensions();
+
+        // unlike the carving algorithms this starts from an open chamber and adds walls,
+        // anything outside the cell lattice (the last row / column on even sizes) stays wall
+        for y in 0..rows * 2 - 1 {
+            for x in 0..cols * 2 - 1 {
+                self.carve(Coord(x, y));
+                self.visit(Coord(x, y));
+            }
+        }
+
+        // chambers are (x, y, width, height) in cells, kept on a stack rather than recursing
+        let mut chambers = vec![(0, 0, cols, rows)];
+        while let Some((cx, cy, w, h)) = chambers.pop() {
+            // split across the longer side, a chamber one cell wide can only be split one way
+            let vertical = match (w, h) {
+                (0..=1, 0..=1) => continue,
+                (1, _) => false,
+                (_, 1) => true,
+                _ => match w.cmp(&h) {
+                    Ordering::Greater => true,
+                    Ordering::Less => false,
+                    Ordering::Equal => rng.gen(),
+                },
+            };
+
+            if vertical {
+                // the wall goes between cell columns split - 1 and split,
+                // running from corner to corner of the chamber with a single gap
+                let split = gen_coord(rng, cx + 1..cx + w);
+                let gap = gen_coord(rng, cy..cy + h) * 2;
+                let x = split * 2 - 1;
+                for y in (cy * 2).saturating_sub(1)..(cy + h) * 2 {
+                    if y != gap {
+                        self.set_tile(Coord(x, y), TileState::Wall);
+                    }
+                }
+
+                chambers.push((cx, cy, split - cx, h));
+                chambers.push((split, cy, cx + w - split, h));
+            } else {
+                let split = gen_coord(rng, cy + 1..cy + h);
+                let gap = gen_coord(rng, cx..cx + w) * 2;
+                let y = split * 2 - 1;
+                for x in (cx * 2).saturating_sub(1)..(cx + w) * 2 {
+                    if x != gap {
+                        self.set_tile(Coord(x, y), TileState::Wall);
+                    }
+                }
+
+                chambers.push((cx, cy, w, split - cy));
+                chambers.push((cx, split, w, cy + h - split));
+            }
+        }
+
+        self.place_start_end();
+    }
+}
@@ -0,0 +1,79 @@
+use core::ops::Range;
+
+use rand::{Error, Rng, RngCore, SeedableRng};
+
+// xoshiro256++, the generator behind SmallRng on 64 bit platforms. SmallRng switches to a
+// different generator on 32 bit platforms, so the builder uses this directly to make a seed
+// give the same maze everywhere. seeding from a u64 matches SmallRng::seed_from_u64, so
+// the mazes are the same as they always were on 64 bit platforms
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PortableRng {
+    s: [u64; 4],
+}
+
+impl SeedableRng for PortableRng {
+    type Seed = [u8; 32];
+
+    fn from_seed(seed: [u8; 32]) -> Self {
+        // an all zero state would only ever generate zeroes
+        if seed.iter().all(|&byte| byte == 0) {
+            return Self::seed_from_u64(0);
+        }
+
+        let mut s = [0; 4];
+        for (word, chunk) in s.iter_mut().zip(seed.chunks_exact(8)) {
+            let mut bytes = [0; 8];
+            bytes.copy_from_slice(chunk);
+            *word = u64::from_le_bytes(bytes);
+        }
+        Self { s }
+    }
+}
+
+impl RngCore for PortableRng {
+    fn next_u32(&mut self) -> u32 {
+        // the lowest bits are the weakest so the upper half is used
+        (self.next_u64() >> 32) as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let result = self.s[0]
+            .wrapping_add(self.s[3])
+            .rotate_left(23)
+            .wrapping_add(self.s[0]);
+
+        let t = self.s[1] << 17;
+        self.s[2] ^= self.s[0];
+        self.s[3] ^= self.s[1];
+        self.s[1] ^= self.s[2];
+        self.s[0] ^= self.s[3];
+        self.s[2] ^= t;
+        self.s[3] = self.s[3].rotate_left(45);
+
+        result
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(8) {
+            let bytes = self.next_u64().to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+// a random index in a range. sampling a usize draws a different amount from the RNG on 32 and
+// 64 bit platforms, so every index goes through a u64 to keep seeds portable
+pub(crate) fn gen_index<R: Rng + ?Sized>(rng: &mut R, range: Range<usize>) -> usize {
+    rng.gen_range(range.start as u64..range.end as u64) as usize
+}
+
+// a random component of a tile in a range, drawn the same way as gen_index
+pub(crate) fn gen_coord<R: Rng + ?Sized>(rng: &mut R, range: Range<u32>) -> u32 {
+    // the sample is inside of the range, so it always fits back into a u32
+    rng.gen_range(u64::from(range.start)..u64::from(range.end)) as u32
+}
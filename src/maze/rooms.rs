@@ -0,0 +1,149 @@
+use alloc::vec::Vec;
+
+use rand::{prelude::SliceRandom, Rng};
+
+use super::{Coord, Direction, Maze, TileState};
+
+// how many spots are tried for each room before giving up on it
+const ROOM_ATTEMPTS: usize = 50;
+
+impl Maze {
+    /// Opens up `count` rectangular rooms between `min` and `max` tiles along each side, for
+    /// dungeon style layouts. Meant to run after the maze is populated.
+    ///
+    /// Rooms stay inside the maze and are kept at least one tile apart so they never overlap
+    /// or merge. Each room is joined to the passages around it, knocking through a door if it
+    /// doesn't already touch one. A room which doesn't fit anywhere after a few tries is left
+    /// out, so fewer rooms can be placed. Returns the number of rooms placed.
+    ///
+    #[cfg_attr(feature = "std", doc = "```")]
+    #[cfg_attr(not(feature = "std"), doc = "```ignore")]
+    /// use labyrinth::{Maze, TileState};
+    /// use rand::{rngs::SmallRng, SeedableRng};
+    ///
+    /// let mut rng = SmallRng::seed_from_u64(7);
+    /// let mut maze = Maze::new(41, 31);
+    /// maze.populate(&mut rng);
+    /// let before = maze.clone();
+    ///
+    /// let rooms = maze.add_rooms(&mut rng, 4, 3, 7);
+    /// assert!(rooms > 0 && rooms <= 4);
+    /// assert!(maze.solve_bfs().is_some());
+    ///
+    /// // every tile knocked through for a room or a door can be walked to from the start
+    /// let (x, y) = maze.start().unwrap();
+    /// let reachable = maze.reachable_from(x, y);
+    /// let carved: Vec<_> = (0..31)
+    ///     .flat_map(|y| (0..41).map(move |x| (x, y)))
+    ///     .filter(|&(x, y)| {
+    ///         let wall = Some(TileState::Wall);
+    ///         before.tile(x, y) == wall && maze.tile(x, y) != wall
+    ///     })
+    ///     .collect();
+    /// assert!(!carved.is_empty());
+    /// assert!(carved.iter().all(|&(x, y)| reachable.get(x, y) == Some(&true)));
+    /// ```
+    pub fn add_rooms<R: Rng + ?Sized>(
+        &mut self,
+        rng: &mut R,
+        count: usize,
+        min: u32,
+        max: u32,
+    ) -> usize {
+        let (min, max) = (min.min(max).max(1), max.max(min).max(1));
+
+        let mut rooms: Vec<Room> = Vec::new();
+        for _ in 0..count {
+            let room = (0..ROOM_ATTEMPTS).find_map(|_| {
+                let (width, height) = (rng.gen_range(min..=max), rng.gen_range(min..=max));
+                if width > self.width || height > self.height {
+                    return None;
+                }
+
+                let room = Room {
+                    x: rng.gen_range(0..=self.width - width),
+                    y: rng.gen_range(0..=self.height - height),
+                    width,
+                    height,
+                };
+                (!rooms.iter().any(|other| room.touches(other))).then_some(room)
+            });
+
+            if let Some(room) = room {
+                self.carve_room(rng, &room);
+                rooms.push(room);
+            }
+        }
+
+        rooms.len()
+    }
+
+    // carves out the inside of a room, leaving the start and end where they are,
+    // then adds a door if none of the passages around it lead in
+    fn carve_room<R: Rng + ?Sized>(&mut self, rng: &mut R, room: &Room) {
+        for tile in room.tiles() {
+            if self.data.at(tile) == Some(&TileState::Wall) {
+                self.carve(tile);
+            }
+        }
+
+        // the walls just outside of the room, paired with the tile behind each of them
+        let mut doorways = Vec::new();
+        for tile in room.tiles() {
+            for &direction in Direction::ALL.iter() {
+                let Some(outside) = self.step(tile, direction).filter(|&t| !room.contains(t))
+                else {
+                    continue;
+                };
+                if self.is_passage(outside) {
+                    return;
+                }
+                if let Some(behind) = self.step(outside, direction) {
+                    doorways.push((outside, behind));
+                }
+            }
+        }
+
+        let doors: Vec<_> = doorways
+            .into_iter()
+            .filter(|&(_, behind)| self.is_passage(behind))
+            .map(|(door, _)| door)
+            .collect();
+        if let Some(&door) = doors.choose(rng) {
+            self.carve(door);
+        }
+    }
+}
+
+// a rectangle of tiles
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+struct Room {
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+}
+
+impl Room {
+    fn contains(&self, Coord(x, y): Coord) -> bool {
+        (self.x..self.x + self.width).contains(&x) && (self.y..self.y + self.height).contains(&y)
+    }
+
+    // whether the two rooms overlap or are right next to each other
+    fn touches(&self, other: &Room) -> bool {
+        self.x <= other.x + other.width
+            && other.x <= self.x + self.width
+            && self.y <= other.y + other.height
+            && other.y <= self.y + self.height
+    }
+
+    fn tiles(&self) -> impl Iterator<Item = Coord> {
+        let Room {
+            x,
+            y,
+            width,
+            height,
+        } = *self;
+        (y..y + height).flat_map(move |y| (x..x + width).map(move |x| Coord(x, y)))
+    }
+}
@@ -0,0 +1,567 @@
+use alloc::{
+    collections::{BTreeMap, BinaryHeap, VecDeque},
+    vec,
+    vec::Vec,
+};
+use core::{cmp::Ordering, convert::TryFrom};
+
+#[cfg(feature = "std")]
+use grid::Grid;
+use rand::Rng;
+
+use super::{coord::widen, Coord, Maze, TileState, Tiles};
+
+impl Maze {
+    /// The shortest path from the start to the end, for drawing the solution yourself. This is
+    /// the path `save_solution_to_file` and the text renderers mark, nothing gets rendered.
+    ///
+    /// `None` is returned if the start or end is missing or the end can't be reached.
+    ///
+    #[cfg_attr(feature = "std", doc = "```")]
+    #[cfg_attr(not(feature = "std"), doc = "```ignore")]
+    /// use labyrinth::Maze;
+    ///
+    /// let maze = Maze::from_ascii("S #\n# E").unwrap();
+    /// assert_eq!(maze.solution().unwrap(), [(0, 0), (1, 0), (1, 1), (2, 1)]);
+    ///
+    /// let walled_in = Maze::from_ascii("S#\n#E").unwrap();
+    /// assert_eq!(walled_in.solution(), None);
+    /// assert_eq!(Maze::new(5, 5).solution(), None);
+    /// ```
+    pub fn solution(&self) -> Option<Vec<(usize, usize)>> {
+        self.solve_bfs()
+    }
+
+    /// Finds the shortest path from the start to the end using a breadth first search.
+    ///
+    /// The path includes both the start and end cells, `None` is returned
+    /// if either is missing or the end can't be reached.
+    ///
+    #[cfg_attr(feature = "std", doc = "```")]
+    #[cfg_attr(not(feature = "std"), doc = "```ignore")]
+    /// use labyrinth::Maze;
+    ///
+    /// let maze = Maze::from_ascii("S #\n# #\n  E")?;
+    /// assert_eq!(
+    ///     maze.solve_bfs(),
+    ///     Some(vec![(0, 0), (1, 0), (1, 1), (1, 2), (2, 2)])
+    /// );
+    ///
+    /// let unreachable = Maze::from_ascii("S #\n###\n  E")?;
+    /// assert_eq!(unreachable.solve_bfs(), None);
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn solve_bfs(&self) -> Option<Vec<(usize, usize)>> {
+        self.solution_tiles().map(to_tiles)
+    }
+
+    // the shortest path from the start to the end, which every solution is drawn from
+    pub(super) fn solution_tiles(&self) -> Option<Vec<Coord>> {
+        let (start, end) = (self.start?, self.end?);
+        self.path_between(start, end)
+    }
+
+    /// Finds the shortest path from the start to whichever of `goals` is closest, for mazes
+    /// with more than one way out. Returns the path along with the goal it leads to.
+    ///
+    /// Goals on walls or outside of the maze are ignored, and when two goals are just as close
+    /// either one may be picked. `None` is returned if the start is missing or none of the
+    /// goals can be reached.
+    ///
+    #[cfg_attr(feature = "std", doc = "```")]
+    #[cfg_attr(not(feature = "std"), doc = "```ignore")]
+    /// use labyrinth::Maze;
+    ///
+    /// let maze = Maze::from_ascii("S    \n#### \n     ")?;
+    /// let goals = [(0, 2), (4, 0), (2, 1), (3, 2)];
+    ///
+    /// // (2, 1) is a wall and (0, 2) is furthest away round the end of the wall
+    /// let (path, goal) = maze.solve_to_any(&goals).unwrap();
+    /// assert_eq!(goal, (4, 0));
+    /// assert_eq!(path.len(), 5);
+    ///
+    /// let (path, goal) = maze.solve_to_any(&goals[..1]).unwrap();
+    /// assert_eq!(goal, (0, 2));
+    /// assert_eq!(path.len(), 11);
+    ///
+    /// assert_eq!(maze.solve_to_any(&[(2, 1), (9, 9)]), None);
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn solve_to_any(&self, goals: &[Tile]) -> Option<(Vec<Tile>, Tile)> {
+        let mut is_goal: Tiles<bool> = self.blank_tiles();
+        let goals = goals.iter().filter_map(|&goal| Coord::try_from(goal).ok());
+        for goal in goals.filter(|&goal| self.is_passage(goal)) {
+            if let Some(tile) = is_goal.at_mut(goal) {
+                *tile = true;
+            }
+        }
+
+        let path = self.path_to_nearest(self.start?, |tile| is_goal.at(tile) == Some(&true))?;
+        let goal = *path.last()?;
+        Some((to_tiles(path), goal.into()))
+    }
+
+    /// Walks from the start to the end with Trémaux's algorithm, the way a person can solve a
+    /// maze by hand by chalking a mark on every passage each time they walk down it.
+    ///
+    /// Only the passages next to the current tile are ever looked at, so unlike `solve_bfs`
+    /// the returned path isn't necessarily the shortest, it is the route left marked once
+    /// after the walk. `None` is returned if either the start or end is missing or the end
+    /// can't be reached.
+    ///
+    /// ```
+    /// use labyrinth::{MazeBuilder, TileState};
+    ///
+    /// let maze = MazeBuilder::new().width(31).height(31).seed(7).build();
+    /// let path = maze.solve_tremaux().unwrap();
+    /// let (x, y) = *path.last().unwrap();
+    /// assert_eq!(maze.tile(x, y), Some(TileState::End));
+    /// // a perfect maze only has one route, so it is the shortest
+    /// assert_eq!(path, maze.solve_bfs().unwrap());
+    /// ```
+    pub fn solve_tremaux(&self) -> Option<Vec<(usize, usize)>> {
+        let (start, end) = (self.start?, self.end?);
+
+        // how many times each passage between two tiles has been walked, stored smallest tile first
+        let mut marks = BTreeMap::new();
+        let key = |a: Coord, b: Coord| (a.min(b), a.max(b));
+
+        // the walk so far without the parts which were backtracked, the passages marked once
+        let mut path = vec![start];
+        let mut previous = None;
+        while let Some(&tile) = path.last() {
+            if tile == end {
+                return Some(to_tiles(path));
+            }
+
+            let neighbours = self.passage_neighbours(tile);
+            let marked = |neighbour| marks.get(&key(tile, neighbour)).copied().unwrap_or(0);
+
+            // coming down a fresh passage into a tile that has been seen before means a loop, so
+            // turn straight back. otherwise take the least walked passage, never one walked twice
+            let seen_before = neighbours
+                .iter()
+                .any(|&neighbour| Some(neighbour) != previous && marked(neighbour) > 0);
+            let next = match previous {
+                Some(previous) if seen_before && marked(previous) == 1 => previous,
+                _ => neighbours
+                    .iter()
+                    .copied()
+                    .filter(|&neighbour| marked(neighbour) < 2)
+                    .min_by_key(|&neighbour| (marked(neighbour), Some(neighbour) == previous))?,
+            };
+
+            *marks.entry(key(tile, next)).or_insert(0_u8) += 1;
+            if path.len() >= 2 && path[path.len() - 2] == next {
+                path.pop();
+            } else {
+                path.push(next);
+            }
+            previous = Some(tile);
+        }
+
+        None
+    }
+
+    /// Finds the longest shortest path in the maze, returning it along with its length in steps.
+    ///
+    /// Uses a double breadth first search: the tile furthest from any passage is one end of the
+    /// longest path, and the tile furthest from that is the other. Only the part of the maze
+    /// connected to the first passage tile is searched, and a maze with no passages gives an
+    /// empty path.
+    pub fn longest_path(&self) -> (Vec<(usize, usize)>, usize) {
+        let (path, length) = self.longest_path_tiles();
+        (to_tiles(path), length)
+    }
+
+    fn longest_path_tiles(&self) -> (Vec<Coord>, usize) {
+        let Some(any) = self.positions().find(|&tile| self.is_passage(tile)) else {
+            return (Vec::new(), 0);
+        };
+
+        let (a, _) = self.furthest_from(any);
+        let (b, length) = self.furthest_from(a);
+        let path = self.path_between(a, b).unwrap_or_default();
+        (path, length)
+    }
+
+    /// Moves the start and end onto the two ends of the longest path, making the maze as hard as
+    /// it can be.
+    ///
+    #[cfg_attr(feature = "std", doc = "```")]
+    #[cfg_attr(not(feature = "std"), doc = "```ignore")]
+    /// use labyrinth::Maze;
+    ///
+    /// let mut maze = Maze::from_ascii("  S E  \n#######")?;
+    /// maze.place_hardest_start_end();
+    /// // the two ends of a straight corridor are as far apart as it gets
+    /// assert_eq!(maze.to_ascii(), "E     S\n#######");
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn place_hardest_start_end(&mut self) {
+        for tile in self.positions() {
+            if matches!(self.data.at(tile), Some(TileState::Start | TileState::End)) {
+                self.carve(tile);
+            }
+        }
+
+        let (path, _) = self.longest_path_tiles();
+        if let (Some(&start), Some(&end)) = (path.first(), path.last()) {
+            self.set_tile(start, TileState::Start);
+            if end != start {
+                self.set_tile(end, TileState::End);
+            }
+        }
+    }
+
+    /// Generates `width` x `height` mazes until one has a solution at least `min_len` tiles long,
+    /// counting the start and end, giving up after `max_attempts` mazes. The start and end of
+    /// each maze go at the ends of its longest path to give it the best chance.
+    ///
+    /// Returns `None` if no maze was long enough, straight away when `min_len` is more than the
+    /// number of tiles or either dimension is zero.
+    ///
+    /// ```
+    /// use labyrinth::Maze;
+    /// use rand::{rngs::SmallRng, SeedableRng};
+    ///
+    /// let mut rng = SmallRng::seed_from_u64(11);
+    /// let maze = Maze::generate_with_min_solution(15, 15, 40, 100, &mut rng).unwrap();
+    /// assert!(maze.solve_bfs().unwrap().len() >= 40);
+    ///
+    /// assert!(Maze::generate_with_min_solution(5, 5, 26, 100, &mut rng).is_none());
+    /// ```
+    pub fn generate_with_min_solution<R: Rng + ?Sized>(
+        width: u32,
+        height: u32,
+        min_len: usize,
+        max_attempts: usize,
+        rng: &mut R,
+    ) -> Option<Maze> {
+        let tiles = widen(width) * widen(height);
+        if tiles == 0 || min_len > tiles {
+            return None;
+        }
+
+        (0..max_attempts).find_map(|_| {
+            let mut maze = Maze::new(width, height);
+            maze.populate(rng);
+            maze.place_hardest_start_end();
+
+            let length = maze.solve_bfs().map_or(0, |path| path.len());
+            (length >= min_len).then_some(maze)
+        })
+    }
+
+    // the reachable tile furthest from a passage tile, paired with its distance
+    fn furthest_from(&self, from: Coord) -> (Coord, usize) {
+        let distances = self.distances_from(from);
+        self.positions()
+            .filter_map(|tile| distances.at(tile).copied().flatten().map(|d| (tile, d)))
+            .max_by_key(|&(_, distance)| distance)
+            .unwrap_or((from, 0))
+    }
+
+    // the shortest path between two tiles, found by a breadth first search
+    pub(super) fn path_between(&self, start: Coord, end: Coord) -> Option<Vec<Coord>> {
+        self.path_to_nearest(start, |tile| tile == end)
+    }
+
+    // the shortest path from `start` to the closest tile `is_goal` picks out, the search stops
+    // as soon as the first one comes off the queue
+    fn path_to_nearest<F: Fn(Coord) -> bool>(
+        &self,
+        start: Coord,
+        is_goal: F,
+    ) -> Option<Vec<Coord>> {
+        // the tile each tile was first reached from, the start points at itself
+        let mut previous: Tiles<Option<Coord>> = self.blank_tiles();
+        *previous.at_mut(start)? = Some(start);
+
+        let mut queue = VecDeque::from(vec![start]);
+        while let Some(tile) = queue.pop_front() {
+            if is_goal(tile) {
+                return Some(trace_path(&previous, start, tile));
+            }
+
+            for neighbour in self.passage_neighbours(tile) {
+                if let Some(prev @ None) = previous.at_mut(neighbour) {
+                    *prev = Some(tile);
+                    queue.push_back(neighbour);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// The distance of every tile reachable from `from`, found by a breadth first flood fill.
+    pub(super) fn distances_from(&self, from: Coord) -> Tiles<Option<usize>> {
+        let mut distances: Tiles<Option<usize>> = self.blank_tiles();
+        if !self.is_passage(from) {
+            return distances;
+        }
+
+        if let Some(distance) = distances.at_mut(from) {
+            *distance = Some(0);
+        }
+
+        let mut queue = VecDeque::from(vec![(from, 0)]);
+        while let Some((tile, distance)) = queue.pop_front() {
+            for neighbour in self.passage_neighbours(tile) {
+                if let Some(d @ None) = distances.at_mut(neighbour) {
+                    *d = Some(distance + 1);
+                    queue.push_back((neighbour, distance + 1));
+                }
+            }
+        }
+
+        distances
+    }
+
+    /// Which tiles can be reached from `(x, y)` by walking along the passages, indexed by
+    /// `(x, y)` like the maze. Walls are barriers, so starting on a wall or outside of the maze
+    /// reaches nothing and every tile is `false`.
+    ///
+    /// ```
+    /// use labyrinth::Maze;
+    ///
+    /// // two rooms with a solid wall between them
+    /// let maze = Maze::from_ascii("  #  \n  #  ")?;
+    /// let reachable = maze.reachable_from(0, 0);
+    /// assert!(reachable.get(1, 1) == Some(&true));
+    /// assert!(reachable.get(2, 0) == Some(&false));
+    /// assert!(reachable.get(3, 0) == Some(&false));
+    /// assert_eq!(reachable.iter().filter(|&&r| r).count(), 4);
+    ///
+    /// assert!(maze.reachable_from(9, 9).iter().all(|&r| !r));
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn reachable_from(&self, x: usize, y: usize) -> Grid<bool> {
+        let reachable: Tiles<bool> = match Coord::try_from((x, y)) {
+            Ok(from) => self.distances_from(from).map(Option::is_some),
+            Err(_) => self.blank_tiles(),
+        };
+        reachable.to_grid()
+    }
+
+    /// Finds the cheapest path from the start to the end using Dijkstra's algorithm, where
+    /// stepping onto a passage costs whatever `costs` holds for it, e.g. more for mud or water.
+    /// Returns the path along with its total cost, the start itself is free.
+    ///
+    /// The costs are indexed by `(x, y)` like the maze and walls can't be crossed whatever they
+    /// cost. `None` is returned if the costs are a different size to the maze, the start or end
+    /// is missing, the end can't be reached or the cheapest path costs more than a `u32` holds.
+    ///
+    /// ```
+    /// use grid::Grid;
+    /// use labyrinth::Maze;
+    ///
+    /// let maze = Maze::from_ascii("S   E\n ### \n     ").unwrap();
+    /// let mut costs = Grid::init(5, 3, 1);
+    /// *costs.get_mut(2, 0).unwrap() = 100;
+    ///
+    /// // the short way along the top goes through the expensive tile, so it goes round instead
+    /// let (path, cost) = maze.solve_weighted(&costs).unwrap();
+    /// assert_eq!(cost, 8);
+    /// assert_eq!(path.len(), 9);
+    /// assert!(!path.contains(&(2, 0)));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn solve_weighted(&self, costs: &Grid<u32>) -> Option<(Vec<(usize, usize)>, u32)> {
+        let costs = Tiles::from_grid(costs)?;
+        if costs.dimensions() != self.data.dimensions() {
+            return None;
+        }
+        let (start, end) = (self.start?, self.end?);
+
+        let mut previous: Tiles<Option<Coord>> = self.blank_tiles();
+        let mut cost: Tiles<usize> = costs.map(|_| usize::MAX);
+        *previous.at_mut(start)? = Some(start);
+        *cost.at_mut(start)? = 0;
+
+        let mut open = BinaryHeap::new();
+        open.push(MinScored {
+            score: 0,
+            tile: start,
+        });
+
+        while let Some(MinScored { score, tile }) = open.pop() {
+            if tile == end {
+                let total = u32::try_from(score).ok()?;
+                return Some((to_tiles(trace_path(&previous, start, end)), total));
+            }
+
+            // a tile can be queued again after a cheaper way to it turns up
+            if score > *cost.at(tile)? {
+                continue;
+            }
+
+            for neighbour in self.passage_neighbours(tile) {
+                let step = usize::try_from(*costs.at(neighbour)?).ok()?;
+                let next_cost = score.saturating_add(step);
+                let Some(neighbour_cost) = cost.at_mut(neighbour) else {
+                    continue;
+                };
+
+                if next_cost < *neighbour_cost {
+                    *neighbour_cost = next_cost;
+                    *previous.at_mut(neighbour)? = Some(tile);
+                    open.push(MinScored {
+                        score: next_cost,
+                        tile: neighbour,
+                    });
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Finds the shortest path from the start to the end using A* with a manhattan distance
+    /// heuristic, which takes the short way round across the edges of a maze that wraps.
+    ///
+    /// Passing underneath a crossing moves two tiles along a row in one step, so when the maze
+    /// has crossings the distance along the row is halved to never overestimate. This always
+    /// finds a path of the same length as `solve_bfs`, but explores far fewer tiles on mazes
+    /// with large open areas.
+    ///
+    #[cfg_attr(feature = "std", doc = "```")]
+    #[cfg_attr(not(feature = "std"), doc = "```ignore")]
+    /// use labyrinth::{Algorithm, Maze, MazeBuilder};
+    ///
+    /// for seed in 0..8 {
+    ///     for &algorithm in &[Algorithm::RecursiveBacktracker, Algorithm::Weave] {
+    ///         let maze = MazeBuilder::new()
+    ///             .width(31)
+    ///             .height(21)
+    ///             .algorithm(algorithm)
+    ///             .braid(0.5)
+    ///             .seed(seed)
+    ///             .build();
+    ///         let astar = maze.solve_astar().map(|path| path.len());
+    ///         assert_eq!(astar, maze.solve_bfs().map(|path| path.len()));
+    ///     }
+    /// }
+    ///
+    /// // the way under the crossings is shorter than the straight corridor across the top
+    /// let crossings: String = (0..25).map(|x| if x % 2 == 1 { '+' } else { ' ' }).collect();
+    /// let side = format!(" {} ", "#".repeat(23));
+    /// let rows = [
+    ///     format!("S{}E", " ".repeat(23)),
+    ///     side.clone(),
+    ///     side,
+    ///     crossings,
+    ///     "#".repeat(25),
+    /// ];
+    /// let maze = Maze::from_ascii(&rows.join("\n"))?;
+    /// assert_eq!(maze.solve_bfs().map(|path| path.len()), Some(19));
+    /// assert_eq!(maze.solve_astar(), maze.solve_bfs());
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn solve_astar(&self) -> Option<Vec<(usize, usize)>> {
+        let (start, end) = (self.start?, self.end?);
+        let distance = |a: u32, b: u32, len: u32| {
+            let d = a.abs_diff(b);
+            if self.wrap {
+                d.min(len - d)
+            } else {
+                d
+            }
+        };
+        let has_crossings = self
+            .positions()
+            .any(|tile| self.data.at(tile) == Some(&TileState::Crossing));
+        let across_step = if has_crossings { 2 } else { 1 };
+        let heuristic = |Coord(x, y): Coord| {
+            let (dx, dy) = (
+                distance(x, end.0, self.width).div_ceil(across_step),
+                distance(y, end.1, self.height),
+            );
+            usize::try_from(u64::from(dx) + u64::from(dy)).unwrap_or(usize::MAX)
+        };
+
+        let mut previous: Tiles<Option<Coord>> = self.blank_tiles();
+        let mut cost: Tiles<usize> = previous.map(|_| usize::MAX);
+        *previous.at_mut(start)? = Some(start);
+        *cost.at_mut(start)? = 0;
+
+        let mut open = BinaryHeap::new();
+        open.push(MinScored {
+            score: heuristic(start),
+            tile: start,
+        });
+
+        while let Some(MinScored { tile, .. }) = open.pop() {
+            if tile == end {
+                return Some(to_tiles(trace_path(&previous, start, end)));
+            }
+
+            let next_cost = cost.at(tile)? + 1;
+            for neighbour in self.passage_neighbours(tile) {
+                let Some(neighbour_cost) = cost.at_mut(neighbour) else {
+                    continue;
+                };
+
+                if next_cost < *neighbour_cost {
+                    *neighbour_cost = next_cost;
+                    *previous.at_mut(neighbour)? = Some(tile);
+                    open.push(MinScored {
+                        score: next_cost + heuristic(neighbour),
+                        tile: neighbour,
+                    });
+                }
+            }
+        }
+
+        None
+    }
+}
+
+// BinaryHeap is a max heap, so this orders by the reverse of the score to pop the lowest first,
+// ties are broken on the tile so the search order is fully deterministic
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+struct MinScored {
+    score: usize,
+    tile: Coord,
+}
+
+impl Ord for MinScored {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .score
+            .cmp(&self.score)
+            .then_with(|| other.tile.cmp(&self.tile))
+    }
+}
+
+impl PartialOrd for MinScored {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// a tile as the public API hands it out
+type Tile = (usize, usize);
+
+// walk back from the end following the previous pointers
+fn trace_path(previous: &Tiles<Option<Coord>>, start: Coord, end: Coord) -> Vec<Coord> {
+    let mut path = vec![end];
+    let mut tile = end;
+    while tile != start {
+        match previous.at(tile).copied().flatten() {
+            Some(prev) => tile = prev,
+            None => break,
+        }
+        path.push(tile);
+    }
+
+    path.reverse();
+    path
+}
+
+// the public API hands tiles out as (x, y) pairs of usizes
+fn to_tiles(path: Vec<Coord>) -> Vec<(usize, usize)> {
+    path.into_iter().map(Into::into).collect()
+}
@@ -0,0 +1,106 @@
+use alloc::{vec, vec::Vec};
+
+use super::{Coord, Maze, TileState};
+
+impl Maze {
+    /// Fills the dead end stubs which are at most `max_stub_len` tiles long back in with wall,
+    /// tidying up the maze without adding any loops. Meant to run after the maze is populated.
+    ///
+    /// A stub is the run of passages from a dead end back to the junction it branches off. Filling
+    /// one in can leave a longer stub behind, so this keeps going until every stub left is longer
+    /// than `max_stub_len`. Stubs holding the start, the end or a crossing are never filled.
+    ///
+    /// ```
+    /// use labyrinth::{Maze, TileState};
+    /// use rand::{rngs::SmallRng, SeedableRng};
+    ///
+    /// // the length of the stub running back from a dead end to the junction it branches off
+    /// fn stub_len(maze: &Maze, dead_end: (usize, usize)) -> Option<usize> {
+    ///     let is_empty = |(x, y)| maze.tile(x, y) == Some(TileState::Empty);
+    ///     let neighbours = |(x, y)| maze.open_neighbours(x, y).collect::<Vec<_>>();
+    ///     if !is_empty(dead_end) || neighbours(dead_end).len() != 1 {
+    ///         return None;
+    ///     }
+    ///
+    ///     let (mut previous, mut tile, mut len) = (dead_end, neighbours(dead_end)[0], 1);
+    ///     loop {
+    ///         let next = neighbours(tile);
+    ///         match next.len() {
+    ///             2 if is_empty(tile) => {}
+    ///             n if n >= 3 => return Some(len),
+    ///             _ => return None,
+    ///         }
+    ///         len += 1;
+    ///         let following = next.into_iter().find(|&n| n != previous)?;
+    ///         previous = tile;
+    ///         tile = following;
+    ///     }
+    /// }
+    ///
+    /// let mut maze = Maze::new(41, 41);
+    /// maze.populate(&mut SmallRng::seed_from_u64(4));
+    /// let dead_ends = maze.stats().dead_ends;
+    /// let tiles: Vec<_> = (0..41).flat_map(|y| (0..41).map(move |x| (x, y))).collect();
+    /// assert!(tiles.iter().any(|&tile| stub_len(&maze, tile).is_some_and(|len| len <= 3)));
+    ///
+    /// maze.sparsify(3);
+    /// assert!(maze.stats().dead_ends < dead_ends);
+    /// assert!(tiles.iter().all(|&tile| stub_len(&maze, tile).map_or(true, |len| len > 3)));
+    /// assert!(maze.is_perfect());
+    /// assert!(maze.solve_bfs().is_some());
+    /// ```
+    pub fn sparsify(&mut self, max_stub_len: usize) {
+        if max_stub_len == 0 {
+            return;
+        }
+
+        loop {
+            let dead_ends: Vec<_> = self
+                .positions()
+                .filter(|&tile| self.is_dead_end(tile))
+                .collect();
+
+            let mut filled = false;
+            for tile in dead_ends {
+                if let Some(stub) = self.short_stub(tile, max_stub_len) {
+                    for tile in stub {
+                        self.set_tile(tile, TileState::Wall);
+                    }
+                    filled = true;
+                }
+            }
+
+            if !filled {
+                break;
+            }
+        }
+    }
+
+    // the tiles of the stub running back from a dead end to its junction, as long as it is
+    // no more than `max_len` tiles long and could be filled in
+    fn short_stub(&self, dead_end: Coord, max_len: usize) -> Option<Vec<Coord>> {
+        let is_empty = |tile: Coord| self.data.at(tile) == Some(&TileState::Empty);
+        if !is_empty(dead_end) || !self.is_dead_end(dead_end) {
+            return None;
+        }
+
+        let mut stub = vec![dead_end];
+        let (mut previous, mut tile) = (dead_end, self.passage_neighbours(dead_end)[0]);
+        loop {
+            let neighbours = self.passage_neighbours(tile);
+            if neighbours.len() >= 3 {
+                return Some(stub);
+            }
+
+            // anything but a plain corridor means this isn't a stub off a junction
+            if neighbours.len() != 2 || !is_empty(tile) || stub.len() == max_len {
+                return None;
+            }
+
+            stub.push(tile);
+            let next = neighbours.into_iter().find(|&n| n != previous)?;
+            previous = tile;
+            tile = next;
+        }
+    }
+}
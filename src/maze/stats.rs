@@ -0,0 +1,231 @@
+use alloc::{vec, vec::Vec};
+
+use super::{Coord, Direction, Maze, Tiles};
+
+// how much a decision point on the solution adds to the difficulty, over just walking a step
+const JUNCTION_WEIGHT: f64 = 2.0;
+// and how much a branch off the solution which only leads to dead ends adds
+const FALSE_BRANCH_WEIGHT: f64 = 5.0;
+
+/// Counts of the passage tiles of a maze, classified by how many of their neighbours are open.
+///
+/// Isolated passage tiles with no open neighbours aren't counted in any of the classes.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct MazeStats {
+    /// passages with a single open neighbour
+    pub dead_ends: usize,
+    /// passages with three open neighbours
+    pub junctions: usize,
+    /// passages with two open neighbours
+    pub corridors: usize,
+    /// passages with all four neighbours open
+    pub crossings: usize,
+}
+
+impl Maze {
+    /// Classifies every passage tile by how many of its four neighbours are open, walls and
+    /// the outside of the grid count as closed. Handy for comparing the texture of algorithms.
+    ///
+    #[cfg_attr(feature = "std", doc = "```")]
+    #[cfg_attr(not(feature = "std"), doc = "```ignore")]
+    /// use labyrinth::{Maze, MazeStats};
+    ///
+    /// // a T, with dead ends at the start, the end and the foot
+    /// let maze = Maze::from_ascii("S   E\n## ##\n## ##")?;
+    /// let stats = maze.stats();
+    /// assert_eq!(
+    ///     stats,
+    ///     MazeStats {
+    ///         dead_ends: 3,
+    ///         junctions: 1,
+    ///         corridors: 3,
+    ///         crossings: 0,
+    ///     }
+    /// );
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn stats(&self) -> MazeStats {
+        let mut stats = MazeStats::default();
+
+        for tile in self.positions().filter(|&tile| self.is_passage(tile)) {
+            match self.passage_neighbours(tile).len() {
+                1 => stats.dead_ends += 1,
+                2 => stats.corridors += 1,
+                3 => stats.junctions += 1,
+                4 => stats.crossings += 1,
+                _ => {}
+            }
+        }
+
+        stats
+    }
+
+    /// Whether the passages form a perfect maze, a spanning tree with exactly one route between
+    /// any two passage tiles. A maze without any passages isn't perfect.
+    ///
+    /// ```
+    /// use labyrinth::MazeBuilder;
+    ///
+    /// let builder = MazeBuilder::new().width(31).height(21).seed(3);
+    /// assert!(builder.clone().build().is_perfect());
+    ///
+    /// // braiding knocks out dead ends, which adds loops
+    /// assert!(!builder.braid(1.0).build().is_perfect());
+    /// ```
+    pub fn is_perfect(&self) -> bool {
+        let passages: Vec<_> = self
+            .positions()
+            .filter(|&tile| self.is_passage(tile))
+            .collect();
+        let Some(&first) = passages.first() else {
+            return false;
+        };
+
+        // every passage has to be reachable from the first one for the maze to be connected
+        let distances = self.distances_from(first);
+        let reachable = distances.iter().flatten().count();
+
+        // and a connected graph is a tree only if it has one less edge than it has nodes,
+        // each edge is counted from both of its ends
+        let ends: usize = passages
+            .iter()
+            .map(|&tile| self.passage_neighbours(tile).len())
+            .sum();
+        let edges = ends / 2;
+
+        reachable == passages.len() && edges == passages.len() - 1
+    }
+
+    /// Scores how hard the maze is to solve by hand, higher is harder. Unsolvable mazes score 0.
+    ///
+    /// The score is `steps + 2 * junctions + 5 * false_branches`, where `steps` is the length of
+    /// the shortest solution, `junctions` counts the tiles on it with three or more ways out,
+    /// and `false_branches` counts the separate regions hanging off it which hold a dead end.
+    ///
+    #[cfg_attr(feature = "std", doc = "```")]
+    #[cfg_attr(not(feature = "std"), doc = "```ignore")]
+    /// use labyrinth::Maze;
+    ///
+    /// let corridor = Maze::from_ascii("S     E\n#######")?;
+    /// let branching = Maze::from_ascii("S     E\n# # # #")?;
+    /// // both solutions take the same six steps
+    /// assert_eq!(corridor.difficulty(), 6.0);
+    /// assert_eq!(branching.difficulty(), 6.0 + 2.0 * 3.0 + 5.0 * 3.0);
+    /// assert!(branching.difficulty() > corridor.difficulty());
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn difficulty(&self) -> f64 {
+        let Some(path) = self
+            .start
+            .zip(self.end)
+            .and_then(|(start, end)| self.path_between(start, end))
+        else {
+            return 0.0;
+        };
+
+        let mut on_path: Tiles<bool> = self.blank_tiles();
+        for &tile in &path {
+            if let Some(tile) = on_path.at_mut(tile) {
+                *tile = true;
+            }
+        }
+
+        let junctions = path
+            .iter()
+            .filter(|&&tile| self.passage_neighbours(tile).len() >= 3)
+            .count();
+
+        // flood each branch without crossing the path, a branch which loops
+        // around to the path again is only counted the first time it is seen
+        let mut seen = on_path.clone();
+        let mut false_branches = 0;
+        for &tile in &path {
+            for branch in self.passage_neighbours(tile) {
+                if seen.at(branch) != Some(&true) && self.branch_has_dead_end(branch, &mut seen) {
+                    false_branches += 1;
+                }
+            }
+        }
+
+        let steps = path.len() - 1;
+        steps as f64
+            + JUNCTION_WEIGHT * junctions as f64
+            + FALSE_BRANCH_WEIGHT * false_branches as f64
+    }
+
+    /// Counts the turns along the shortest solution, the tiles where it leaves in a different
+    /// direction to the one it came in from. Dividing by the length of the solution gives how
+    /// twisty it is, a winding route feels harder than a straight one of the same length.
+    ///
+    /// `None` is returned if the start or end is missing or the end can't be reached.
+    ///
+    #[cfg_attr(feature = "std", doc = "```")]
+    #[cfg_attr(not(feature = "std"), doc = "```ignore")]
+    /// use labyrinth::Maze;
+    ///
+    /// assert_eq!(Maze::from_ascii("S  E")?.solution_turns(), Some(0));
+    ///
+    /// let l_shape = Maze::from_ascii("S##\n ##\n  E")?;
+    /// assert_eq!(l_shape.solution_turns(), Some(1));
+    ///
+    /// let zig_zag = Maze::from_ascii("S ###\n#  ##\n## E#")?;
+    /// assert_eq!(zig_zag.solution_turns(), Some(4));
+    ///
+    /// assert_eq!(Maze::from_ascii("S#E")?.solution_turns(), None);
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn solution_turns(&self) -> Option<usize> {
+        let path = self.path_between(self.start?, self.end?)?;
+        let directions: Vec<_> = path
+            .windows(2)
+            .map(|step| self.direction_between(step[0], step[1]))
+            .collect();
+
+        Some(
+            directions
+                .windows(2)
+                .filter(|turn| turn[0] != turn[1])
+                .count(),
+        )
+    }
+
+    // which way a step along a path goes, a step can wrap around the edges or pass under a
+    // crossing to the tile beyond it
+    fn direction_between(&self, from: Coord, to: Coord) -> Option<Direction> {
+        // a small maze which wraps can have both, the tile right next door is the real step
+        let next_door = Direction::ALL
+            .iter()
+            .copied()
+            .find(|&direction| self.step(from, direction) == Some(to));
+
+        next_door.or_else(|| {
+            Direction::ALL.iter().copied().find(|&direction| {
+                let next = self.step(from, direction);
+                next.and_then(|next| self.step(next, direction)) == Some(to)
+            })
+        })
+    }
+
+    // flood fills the region reachable from a tile through unseen tiles, marking it all as seen
+    fn branch_has_dead_end(&self, from: Coord, seen: &mut Tiles<bool>) -> bool {
+        let mut dead_end = false;
+        let mut stack = vec![from];
+        if let Some(tile) = seen.at_mut(from) {
+            *tile = true;
+        }
+
+        while let Some(tile) = stack.pop() {
+            let neighbours = self.passage_neighbours(tile);
+            dead_end |= neighbours.len() == 1;
+
+            for neighbour in neighbours {
+                if let Some(tile @ false) = seen.at_mut(neighbour) {
+                    *tile = true;
+                    stack.push(neighbour);
+                }
+            }
+        }
+
+        dead_end
+    }
+}
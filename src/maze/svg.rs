@@ -0,0 +1,190 @@
+use std::{ffi::OsStr, fmt::Write, fs, path::Path};
+
+use anyhow::Context;
+use rgb::RGB8;
+
+use super::{Coord, Maze, TileState};
+
+// the size of a tile in SVG units when saving to a file
+const SVG_CELL_SIZE: u32 = 10;
+
+impl Maze {
+    /// Saves the maze as an SVG image with each tile 10 units across, see `to_svg`.
+    pub fn save_svg<S: AsRef<OsStr> + ?Sized>(&self, s: &S) -> anyhow::Result<()> {
+        self.save_svg_scaled(s, 1)
+    }
+
+    /// Saves the maze as an SVG image with each tile `10 * scale` units across, so it opens at
+    /// the same size as a PNG saved with `save_to_file_scaled` and ten times the scale.
+    ///
+    /// ```
+    /// use labyrinth::MazeBuilder;
+    ///
+    /// let maze = MazeBuilder::new().width(11).height(7).seed(1).build();
+    /// let path = std::env::temp_dir().join("labyrinth_scaled.svg");
+    /// maze.save_svg_scaled(&path, 3)?;
+    /// let svg = std::fs::read_to_string(&path)?;
+    /// assert!(svg.lines().next().unwrap().contains(r#"width="330" height="210""#));
+    /// assert_eq!(svg, maze.to_svg(30));
+    /// assert!(maze.save_svg_scaled(&path, 0).is_err());
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn save_svg_scaled<S: AsRef<OsStr> + ?Sized>(
+        &self,
+        s: &S,
+        scale: u32,
+    ) -> anyhow::Result<()> {
+        if scale == 0 {
+            anyhow::bail!("Cannot draw the maze at a scale of 0.");
+        }
+
+        let path = Path::new(s);
+        fs::write(path, self.to_svg(SVG_CELL_SIZE * scale))
+            .with_context(|| format!("Failed to write the SVG to {}.", path.display()))
+    }
+
+    /// Saves the maze as an SVG image with the shortest path from the start to the end drawn on
+    /// as a line in the `stroke` colour, see `to_svg_solution`.
+    pub fn save_svg_solution<S: AsRef<OsStr> + ?Sized>(
+        &self,
+        s: &S,
+        stroke: &str,
+    ) -> anyhow::Result<()> {
+        let svg = self
+            .to_svg_solution(SVG_CELL_SIZE, stroke, SVG_CELL_SIZE / 3)
+            .context("Failed to solve the maze, the end is not reachable from the start.")?;
+
+        let path = Path::new(s);
+        fs::write(path, svg)
+            .with_context(|| format!("Failed to write the SVG to {}.", path.display()))
+    }
+
+    /// Renders the maze as an SVG image like `to_svg`, with the shortest path from the start
+    /// to the end drawn over the top as a polyline through the centre of every tile on it.
+    ///
+    /// `stroke` is any SVG colour, e.g. `blue` or `#0000ff`, and `stroke_width` is in the same
+    /// units as `cell_size`. `None` is returned if the maze can't be solved.
+    ///
+    /// ```
+    /// use labyrinth::MazeBuilder;
+    ///
+    /// let maze = MazeBuilder::new().width(31).height(17).seed(3).build();
+    /// let svg = maze.to_svg_solution(10, "#0000ff", 3).unwrap();
+    ///
+    /// let line = svg.lines().find(|line| line.starts_with("<polyline")).unwrap();
+    /// let points: Vec<&str> = line.split('"').nth(1).unwrap().split(' ').collect();
+    /// let solution = maze.solution().unwrap();
+    /// assert_eq!(points.len(), solution.len());
+    ///
+    /// // the line runs from the centre of the start tile to the centre of the end tile
+    /// let centre = |(x, y): (usize, usize)| format!("{},{}", x * 10 + 5, y * 10 + 5);
+    /// assert_eq!(points[0], centre(maze.start().unwrap()));
+    /// assert_eq!(points[points.len() - 1], centre(maze.end().unwrap()));
+    /// ```
+    pub fn to_svg_solution(
+        &self,
+        cell_size: u32,
+        stroke: &str,
+        stroke_width: u32,
+    ) -> Option<String> {
+        let solution = self.solution()?;
+        let half = f64::from(cell_size) / 2.0;
+        let points: Vec<String> = solution
+            .iter()
+            .map(|&(x, y)| {
+                format!(
+                    "{},{}",
+                    x as f64 * f64::from(cell_size) + half,
+                    y as f64 * f64::from(cell_size) + half
+                )
+            })
+            .collect();
+
+        let mut svg = self.to_svg(cell_size);
+        svg.truncate(svg.len() - "</svg>\n".len());
+        let _ = writeln!(
+            svg,
+            r#"<polyline points="{}" fill="none" stroke="{}" stroke-width="{}" stroke-linecap="round" stroke-linejoin="round"/>"#,
+            points.join(" "),
+            stroke,
+            stroke_width
+        );
+        svg.push_str("</svg>\n");
+        Some(svg)
+    }
+
+    /// Renders the maze as an SVG image with each tile `cell_size` units across.
+    ///
+    /// Runs of wall tiles along a row are merged into a single rectangle to keep the file small.
+    /// Crossings get thin walls down their east and west sides, so the passage underneath
+    /// is drawn broken.
+    pub fn to_svg(&self, cell_size: u32) -> String {
+        let (width, height) = (self.width * cell_size, self.height * cell_size);
+
+        let mut svg = String::new();
+        let _ = writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+            w = width,
+            h = height,
+        );
+        let _ = writeln!(
+            svg,
+            r#"<rect width="{}" height="{}" fill="{}"/>"#,
+            width,
+            height,
+            hex_colour(&TileState::Empty)
+        );
+
+        for y in 0..self.height {
+            let mut x = 0;
+            while x < self.width {
+                let tile = self.data.at(Coord(x, y)).copied().unwrap_or_default();
+
+                // walls get merged with the walls after them, start / end are always single tiles
+                let mut run = 1;
+                if tile == TileState::Wall {
+                    while self.data.at(Coord(x + run, y)) == Some(&TileState::Wall) {
+                        run += 1;
+                    }
+                }
+
+                if tile == TileState::Crossing {
+                    let side = (cell_size / 5).max(1);
+                    for left in [0, cell_size - side] {
+                        let _ = writeln!(
+                            svg,
+                            r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#,
+                            x * cell_size + left,
+                            y * cell_size,
+                            side,
+                            cell_size,
+                            hex_colour(&TileState::Wall)
+                        );
+                    }
+                } else if tile != TileState::Empty {
+                    let _ = writeln!(
+                        svg,
+                        r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#,
+                        x * cell_size,
+                        y * cell_size,
+                        run * cell_size,
+                        cell_size,
+                        hex_colour(&tile)
+                    );
+                }
+
+                x += run;
+            }
+        }
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+// the colour a tile is drawn in as an SVG colour, e.g. `#00ff00`
+pub(crate) fn hex_colour(tile: &TileState) -> String {
+    let RGB8 { r, g, b } = tile.into();
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
@@ -0,0 +1,183 @@
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::str::FromStr;
+
+use rand::{prelude::SliceRandom, Rng};
+
+use super::{Algorithm, Coord, Maze, TileState};
+
+/// The symmetry of a maze generated by `Maze::populate_symmetric`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Symmetry {
+    /// The right half is the mirror image of the left half.
+    Horizontal,
+    /// The bottom half is the mirror image of the top half.
+    Vertical,
+    /// The maze looks the same after half a turn.
+    Rotational,
+}
+
+impl Symmetry {
+    pub const ALL: [Symmetry; 3] = [
+        Symmetry::Horizontal,
+        Symmetry::Vertical,
+        Symmetry::Rotational,
+    ];
+
+    /// The name used to select the symmetry on the command line.
+    pub fn name(self) -> &'static str {
+        match self {
+            Symmetry::Horizontal => "horizontal",
+            Symmetry::Vertical => "vertical",
+            Symmetry::Rotational => "rotational",
+        }
+    }
+
+    // the tile which a tile is mapped onto in a `width` x `height` maze
+    fn image(self, Coord(x, y): Coord, width: u32, height: u32) -> Coord {
+        match self {
+            Symmetry::Horizontal => Coord(width - 1 - x, y),
+            Symmetry::Vertical => Coord(x, height - 1 - y),
+            Symmetry::Rotational => Coord(width - 1 - x, height - 1 - y),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl FromStr for Symmetry {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Symmetry::ALL
+            .iter()
+            .copied()
+            .find(|symmetry| symmetry.name() == s)
+            .ok_or_else(|| {
+                let names: Vec<_> = Symmetry::ALL.iter().map(|s| s.name()).collect();
+                anyhow::anyhow!(
+                    "Unknown symmetry {:?}, expected one of: {}.",
+                    s,
+                    names.join(", ")
+                )
+            })
+    }
+}
+
+impl Maze {
+    /// Generates the maze with any algorithm so that its walls have the given symmetry.
+    ///
+    /// Only one half of the maze is generated, it is then copied onto the other half and the
+    /// two are joined by a passage across the axis, so everything can still be reached. The
+    /// start and end are placed as usual and don't follow the symmetry. A maze less than three
+    /// tiles across the axis is too small to split and is carved out completely.
+    ///
+    /// ```
+    /// use labyrinth::{Algorithm, Maze, Symmetry, TileState};
+    /// use rand::{rngs::SmallRng, SeedableRng};
+    ///
+    /// let mut maze = Maze::new(31, 21);
+    /// let mut rng = SmallRng::seed_from_u64(3);
+    /// maze.populate_symmetric(Algorithm::Prim, Symmetry::Horizontal, &mut rng);
+    ///
+    /// let is_wall = |x, y| maze.tile(x, y) == Some(TileState::Wall);
+    /// for y in 0..21 {
+    ///     for x in 0..31 {
+    ///         assert_eq!(is_wall(x, y), is_wall(30 - x, y));
+    ///     }
+    /// }
+    /// assert!(maze.solve_bfs().is_some());
+    /// ```
+    pub fn populate_symmetric<R: Rng + ?Sized>(
+        &mut self,
+        algorithm: Algorithm,
+        symmetry: Symmetry,
+        rng: &mut R,
+    ) {
+        let (width, height) = (self.width, self.height);
+
+        // the tiles between the generated half and its image make up the axis
+        let (half_width, half_height) = match symmetry {
+            Symmetry::Horizontal | Symmetry::Rotational => ((width - 1) / 2, height),
+            Symmetry::Vertical => (width, (height - 1) / 2),
+        };
+        if half_width == 0 || half_height == 0 {
+            for tile in self.positions() {
+                self.carve(tile);
+            }
+            self.place_start_end();
+            return;
+        }
+
+        let mut half = Maze::new(half_width, half_height);
+        half.populate_with(algorithm, rng);
+        for tile in half.positions() {
+            let state = match half.data.at(tile) {
+                Some(TileState::Start | TileState::End) => TileState::Empty,
+                Some(&state) => state,
+                None => continue,
+            };
+            self.set_tile(tile, state);
+            self.set_tile(symmetry.image(tile, width, height), state);
+        }
+
+        self.bridge_halves(symmetry, rng);
+        self.place_start_end();
+    }
+
+    // carves a corridor across the axis from the closest passages on either side, along with
+    // its image so the symmetry holds. a corridor which is its own image joins the halves
+    // without adding a loop, as does one which doesn't run alongside any other passages
+    fn bridge_halves<R: Rng + ?Sized>(&mut self, symmetry: Symmetry, rng: &mut R) {
+        let (width, height) = (self.width, self.height);
+
+        // corridors run along the rows for a left / right split and down the columns otherwise
+        let (lines, length) = match symmetry {
+            Symmetry::Horizontal | Symmetry::Rotational => (height, width),
+            Symmetry::Vertical => (width, height),
+        };
+        let along = |line: u32, i: u32| match symmetry {
+            Symmetry::Horizontal | Symmetry::Rotational => Coord(i, line),
+            Symmetry::Vertical => Coord(line, i),
+        };
+        let half = (length - 1) / 2;
+
+        let mut bridges = Vec::new();
+        for line in 0..lines {
+            let from = (0..half).rev().find(|&i| self.is_passage(along(line, i)));
+            let to = (length - half..length).find(|&i| self.is_passage(along(line, i)));
+            let (Some(from), Some(to)) = (from, to) else {
+                continue;
+            };
+
+            let tiles: Vec<_> = (from + 1..to).map(|i| along(line, i)).collect();
+            // going sideways into a crossing would pass underneath it
+            let ends_open = [from, to]
+                .iter()
+                .all(|&i| self.data.at(along(line, i)) == Some(&TileState::Empty));
+            let alone = (from + 1..to).all(|i| {
+                [line.wrapping_sub(1), line + 1]
+                    .iter()
+                    .filter(|&&side| side < lines)
+                    .all(|&side| !self.is_passage(along(side, i)))
+            });
+            // only a half turn moves the line somewhere else, apart from the middle row
+            let own_image = symmetry != Symmetry::Rotational || line == lines - 1 - line;
+
+            bridges.push(((ends_open && alone, own_image), tiles));
+        }
+
+        let Some(best) = bridges.iter().map(|(key, _)| *key).max() else {
+            return;
+        };
+        let best: Vec<_> = bridges
+            .into_iter()
+            .filter(|(key, _)| *key == best)
+            .collect();
+        if let Some((_, tiles)) = best.choose(rng) {
+            for &tile in tiles {
+                self.carve(tile);
+                self.carve(symmetry.image(tile, width, height));
+            }
+        }
+    }
+}
@@ -0,0 +1,357 @@
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::Write;
+#[cfg(feature = "std")]
+use std::{
+    convert::TryFrom,
+    io::{self, IsTerminal},
+};
+
+#[cfg(feature = "std")]
+use anyhow::{bail, Context};
+use rgb::RGB8;
+
+use super::{ColorScheme, Coord, Direction, Maze, TileState, Tiles};
+
+// box drawing glyphs for a wall tile, indexed by which of its neighbours are also walls:
+// 1 = above, 2 = right, 4 = below, 8 = left
+const BOX_GLYPHS: [char; 16] = [
+    '■', '│', '─', '└', '│', '│', '┌', '├', '─', '┘', '─', '┴', '┐', '┤', '┬', '┼',
+];
+
+impl Maze {
+    /// Renders the maze as text, one line per row with `#` for walls, spaces for passages,
+    /// `+` for crossings, and `S` / `E` for the start and end.
+    ///
+    #[cfg_attr(feature = "std", doc = "```")]
+    #[cfg_attr(not(feature = "std"), doc = "```ignore")]
+    /// use labyrinth::Maze;
+    ///
+    /// assert_eq!(Maze::new(4, 2).to_ascii(), "####\n####");
+    ///
+    /// // passages can be read in as dots, they are always written as spaces
+    /// let maze = Maze::from_ascii("S.#.#\n#.+.#\n###.E")?;
+    /// assert_eq!(maze.to_ascii(), "S # #\n# + #\n### E");
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn to_ascii(&self) -> String {
+        self.render_text(|_, tile| match tile {
+            TileState::Wall => '#',
+            TileState::Empty => ' ',
+            TileState::Start => 'S',
+            TileState::End => 'E',
+            TileState::Crossing => '+',
+        })
+    }
+
+    /// Renders the maze as text like `to_ascii` but with every tile two characters wide, so the
+    /// maze comes out roughly square in a terminal where characters are taller than they are wide.
+    ///
+    #[cfg_attr(feature = "std", doc = "```")]
+    #[cfg_attr(not(feature = "std"), doc = "```ignore")]
+    /// use labyrinth::Maze;
+    ///
+    /// let maze = Maze::from_ascii("S #\n# E").unwrap();
+    /// assert_eq!(maze.to_ascii_wide(), "SS  ##\n##  EE");
+    /// assert!(maze.to_ascii_wide().lines().all(|row| row.len() == 2 * 3));
+    /// ```
+    pub fn to_ascii_wide(&self) -> String {
+        self.to_ascii()
+            .lines()
+            .map(|row| {
+                row.chars()
+                    .flat_map(|glyph| [glyph, glyph])
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Renders the maze as text like `to_ascii` with the shortest path from the start to the end
+    /// marked with `*`, the start and end still show as `S` and `E`. An unsolvable maze is
+    /// rendered without a path.
+    ///
+    #[cfg_attr(feature = "std", doc = "```")]
+    #[cfg_attr(not(feature = "std"), doc = "```ignore")]
+    /// use labyrinth::Maze;
+    ///
+    /// // the dead ends on the right are left unmarked
+    /// let maze = Maze::from_ascii("S   \n## #\n#E  ")?;
+    /// assert_eq!(maze.to_ascii_with_solution(), "S** \n##*#\n#E* ");
+    ///
+    /// let unsolvable = Maze::from_ascii("S#E")?;
+    /// assert_eq!(unsolvable.to_ascii_with_solution(), "S#E");
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn to_ascii_with_solution(&self) -> String {
+        let ascii = self.to_ascii();
+        self.mark_solution(&ascii, '*')
+    }
+
+    /// Renders the distance of every passage from the start as text for checking a flood fill
+    /// by eye, `#` for walls and `?` for passages which can't be reached. The values are right
+    /// aligned to the widest one and separated by spaces. Without a start every passage is `?`.
+    ///
+    #[cfg_attr(feature = "std", doc = "```")]
+    #[cfg_attr(not(feature = "std"), doc = "```ignore")]
+    /// use labyrinth::Maze;
+    ///
+    /// let maze = Maze::from_ascii("S  \n## \nE  ").unwrap();
+    /// assert_eq!(maze.to_ascii_distances(), "0 1 2\n# # 3\n6 5 4");
+    /// ```
+    pub fn to_ascii_distances(&self) -> String {
+        let (width, height) = (self.width, self.height);
+        let distances = self.start.map(|start| self.distances_from(start));
+        let distance = |tile| distances.as_ref()?.at(tile).copied().flatten();
+
+        let labels: Vec<Vec<String>> = (0..height)
+            .map(|y| {
+                (0..width)
+                    .map(|x| Coord(x, y))
+                    .map(|tile| match distance(tile) {
+                        _ if !self.is_passage(tile) => "#".to_string(),
+                        Some(distance) => distance.to_string(),
+                        None => "?".to_string(),
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let column = labels.iter().flatten().map(String::len).max().unwrap_or(0);
+        let rows: Vec<String> = labels
+            .iter()
+            .map(|row| {
+                let padded: Vec<_> = row
+                    .iter()
+                    .map(|label| format!("{:>column$}", label, column = column))
+                    .collect();
+                padded.join(" ")
+            })
+            .collect();
+        rows.join("\n")
+    }
+
+    /// Renders the maze as text like `to_box_drawing` with the shortest path from the start to
+    /// the end drawn as a dotted trail of `·`, see `to_ascii_with_solution`.
+    pub fn to_box_drawing_with_solution(&self) -> String {
+        let text = self.to_box_drawing();
+        self.mark_solution(&text, '·')
+    }
+
+    /// Renders the maze as text like `to_ascii`, but joins up the walls with box drawing
+    /// characters.
+    ///
+    #[cfg_attr(feature = "std", doc = "```")]
+    #[cfg_attr(not(feature = "std"), doc = "```ignore")]
+    /// use labyrinth::Maze;
+    ///
+    /// let maze = Maze::from_ascii("###\n#S#\n# E")?;
+    /// assert_eq!(maze.to_box_drawing(), "┌─┐\n│S│\n│ E");
+    ///
+    /// // a wall on its own is a block
+    /// let maze = Maze::from_ascii("S #\n# #\n  E")?;
+    /// assert_eq!(maze.to_box_drawing(), "S │\n■ │\n  E");
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn to_box_drawing(&self) -> String {
+        // the row above is towards decreasing y, which is south
+        let is_wall = |tile: Coord, direction| {
+            let neighbour = tile.neighbour(direction, self.width, self.height);
+            usize::from(neighbour.is_some_and(|n| self.data.at(n) == Some(&TileState::Wall)))
+        };
+
+        self.render_text(|tile, state| match state {
+            TileState::Wall => {
+                let mask = is_wall(tile, Direction::South)
+                    | is_wall(tile, Direction::East) << 1
+                    | is_wall(tile, Direction::North) << 2
+                    | is_wall(tile, Direction::West) << 3;
+                BOX_GLYPHS[mask]
+            }
+            TileState::Empty => ' ',
+            TileState::Start => 'S',
+            TileState::End => 'E',
+            TileState::Crossing => '+',
+        })
+    }
+
+    /// Prints the maze to stdout in colour with `to_ansi`, or as plain `to_ascii` text
+    /// when stdout isn't a terminal so nothing piped out gets escape codes in it.
+    #[cfg(feature = "std")]
+    pub fn print_colored(&self) {
+        println!("{}", self.to_terminal(io::stdout().is_terminal()));
+    }
+
+    /// The text `print_colored` prints, `to_ansi` when `colour` is set and `to_ascii` otherwise.
+    ///
+    /// ```
+    /// use labyrinth::MazeBuilder;
+    ///
+    /// let maze = MazeBuilder::new().width(9).height(5).seed(3).build();
+    /// let plain = maze.to_terminal(false);
+    /// assert!(!plain.contains('\x1b'));
+    /// assert_eq!(plain, maze.to_ascii());
+    /// assert_eq!(plain.lines().count(), 5);
+    /// assert!(plain.lines().all(|line| line.chars().count() == 9));
+    ///
+    /// assert!(maze.to_terminal(true).starts_with("\x1b["));
+    /// ```
+    pub fn to_terminal(&self, colour: bool) -> String {
+        if colour {
+            self.to_ansi()
+        } else {
+            self.to_ascii()
+        }
+    }
+
+    /// Renders the maze for a terminal with ANSI true colour escape codes, in the default colours.
+    ///
+    /// Each character is a `▀` half block with the upper tile as the foreground colour and the
+    /// lower tile as the background, so one line of text covers two rows of tiles and the tiles
+    /// come out roughly square.
+    pub fn to_ansi(&self) -> String {
+        let scheme = ColorScheme::default();
+        let colour = |x, y| self.data.at(Coord(x, y)).map(|&tile| scheme.colour(tile));
+
+        let mut text = String::new();
+        for y in (0..self.height).step_by(2) {
+            for x in 0..self.width {
+                let RGB8 { r, g, b } = colour(x, y).unwrap_or_default();
+                let _ = write!(text, "\x1b[38;2;{};{};{}m", r, g, b);
+                // an odd row out at the bottom leaves the lower half in the terminal's background
+                match colour(x, y + 1) {
+                    Some(RGB8 { r, g, b }) => {
+                        let _ = write!(text, "\x1b[48;2;{};{};{}m", r, g, b);
+                    }
+                    None => text.push_str("\x1b[49m"),
+                }
+                text.push('▀');
+            }
+            text.push_str("\x1b[0m\n");
+        }
+
+        text.pop();
+        text
+    }
+
+    /// Loads a maze from text like `to_ascii` produces, with `#` for walls, spaces or `.` for
+    /// passages, `+` for crossings, and `S` / `E` for the start and end. The size is taken from
+    /// the text, so every row has to be the same length.
+    ///
+    /// ```
+    /// use labyrinth::{Algorithm, Maze, MazeBuilder};
+    ///
+    /// for &algorithm in &[Algorithm::RecursiveBacktracker, Algorithm::Weave] {
+    ///     let maze = MazeBuilder::new()
+    ///         .width(31)
+    ///         .height(21)
+    ///         .seed(8)
+    ///         .algorithm(algorithm)
+    ///         .build();
+    ///     assert_eq!(Maze::from_ascii(&maze.to_ascii())?, maze);
+    /// }
+    ///
+    /// assert_eq!(Maze::from_ascii("S.#\n#.E")?, Maze::from_ascii("S #\n# E")?);
+    /// assert!(Maze::from_ascii("S #\n#E").is_err());
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn from_ascii(s: &str) -> anyhow::Result<Maze> {
+        let rows: Vec<Vec<char>> = s.lines().map(|row| row.chars().collect()).collect();
+        let width = rows.first().map_or(0, Vec::len);
+        if width == 0 {
+            bail!("Cannot load a maze from text without any tiles.");
+        }
+
+        if let Some((y, row)) = rows.iter().enumerate().find(|(_, row)| row.len() != width) {
+            bail!(
+                "Row {} of the maze is {} tiles long but the first row is {}.",
+                y,
+                row.len(),
+                width
+            );
+        }
+
+        let dimension = |n: usize| {
+            u32::try_from(n)
+                .with_context(|| format!("The maze is too big at {}x{} tiles.", width, rows.len()))
+        };
+        let (columns, height) = (dimension(width)?, dimension(rows.len())?);
+
+        let mut data = Tiles::new(columns, height);
+        for (row, y) in rows.iter().zip(0..) {
+            for (&glyph, x) in row.iter().zip(0..) {
+                let tile = match glyph {
+                    '.' => Some(TileState::Empty),
+                    glyph => Maze::tile_from_glyph(glyph),
+                }
+                .with_context(|| {
+                    format!("Unknown tile {:?} at ({}, {}) in the maze.", glyph, x, y)
+                })?;
+                if let Some(t) = data.at_mut(Coord(x, y)) {
+                    *t = tile;
+                }
+            }
+        }
+
+        Ok(Maze::from_tiles(columns, height, data))
+    }
+
+    // the reverse of the glyphs used by to_ascii
+    #[cfg(feature = "std")]
+    pub(super) fn tile_from_glyph(glyph: char) -> Option<TileState> {
+        match glyph {
+            '#' => Some(TileState::Wall),
+            ' ' => Some(TileState::Empty),
+            'S' => Some(TileState::Start),
+            'E' => Some(TileState::End),
+            '+' => Some(TileState::Crossing),
+            _ => None,
+        }
+    }
+
+    // swaps the glyphs of the passages on the solution for `mark`, leaving the start and end
+    fn mark_solution(&self, text: &str, mark: char) -> String {
+        let mut on_path = Tiles::init(self.width, self.height, false);
+        for tile in self.solution_tiles().unwrap_or_default() {
+            if let Some(on) = on_path.at_mut(tile) {
+                *on = true;
+            }
+        }
+
+        let rows = text.lines().zip(0..).map(|(row, y)| {
+            row.chars()
+                .zip(0..)
+                .map(|(glyph, x)| {
+                    let tile = Coord(x, y);
+                    let is_open = matches!(
+                        self.data.at(tile),
+                        Some(TileState::Empty | TileState::Crossing)
+                    );
+                    if is_open && on_path.at(tile) == Some(&true) {
+                        mark
+                    } else {
+                        glyph
+                    }
+                })
+                .collect::<String>()
+        });
+        rows.collect::<Vec<_>>().join("\n")
+    }
+
+    // one character per tile, rows separated by newlines
+    fn render_text<F: Fn(Coord, TileState) -> char>(&self, glyph: F) -> String {
+        (0..self.height)
+            .map(|y| {
+                (0..self.width)
+                    .map(|x| Coord(x, y))
+                    .map(|tile| glyph(tile, self.data.at(tile).copied().unwrap_or_default()))
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
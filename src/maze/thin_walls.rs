@@ -0,0 +1,198 @@
+use alloc::{vec, vec::Vec};
+
+use rgb::RGB8;
+
+use super::{coord::widen, Coord, Direction, Maze, TileState, Tiles};
+
+// the bits of a cell's wall set, a set bit means that side is still walled off
+const WALL_NORTH: u8 = 1;
+const WALL_EAST: u8 = 2;
+const WALL_SOUTH: u8 = 4;
+const WALL_WEST: u8 = 8;
+
+impl Maze {
+    /// Renders the maze with each cell drawn as a `corridor_px` square of open space and thin
+    /// walls `wall_px` pixels wide only on the sides which haven't been carved, so corridors
+    /// and walls can be sized separately. A `wall_px` of 0 leaves the maze without a border.
+    ///
+    /// The start and end only fill the corridor of their cell. Returns the pixels in row order
+    /// along with the width and height of the image, see `thin_wall_dimensions`.
+    ///
+    /// Only mazes made of cells on the even tiles with the walls between them carved away can
+    /// be drawn like this, which is how every `Algorithm` but the recursive backtracker lays
+    /// them out. `None` is returned for any other maze, including one with a solid border as
+    /// that moves the cells onto the odd tiles.
+    ///
+    #[cfg_attr(feature = "std", doc = "```")]
+    #[cfg_attr(not(feature = "std"), doc = "```ignore")]
+    /// use labyrinth::{Algorithm, Maze, MazeBuilder, TileState};
+    /// use rgb::RGB8;
+    ///
+    /// let builder = MazeBuilder::new().width(21).height(15).seed(1);
+    /// let prim = builder.clone().algorithm(Algorithm::Prim).build();
+    /// assert!(prim.render_thin_walls(8, 2).is_some());
+    /// assert!(builder.build().render_thin_walls(8, 2).is_none());
+    ///
+    /// // 2 x 2 cells, with the wall between the two on the left still up
+    /// let maze = Maze::from_ascii("S  \n## \nE  ")?;
+    /// let (pixels, width, height) = maze.render_thin_walls(2, 1).unwrap();
+    /// assert_eq!((width, height), (7, 7));
+    ///
+    /// let glyph = |pixel: &RGB8| match pixel {
+    ///     p if *p == RGB8::from(&TileState::Wall) => '#',
+    ///     p if *p == RGB8::from(&TileState::Start) => 'S',
+    ///     p if *p == RGB8::from(&TileState::End) => 'E',
+    ///     _ => ' ',
+    /// };
+    /// let rows: Vec<String> = pixels
+    ///     .chunks(7)
+    ///     .map(|row| row.iter().map(glyph).collect())
+    ///     .collect();
+    /// assert_eq!(
+    ///     rows,
+    ///     [
+    ///         "#######", "#SS   #", "#SS   #", "####  #", "#EE   #", "#EE   #", "#######",
+    ///     ]
+    /// );
+    ///
+    /// // the tile in the middle is where the corners of all four cells meet
+    /// assert!(Maze::from_ascii("S  \n   \n  E")?.render_thin_walls(2, 1).is_none());
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn render_thin_walls(
+        &self,
+        corridor_px: u32,
+        wall_px: u32,
+    ) -> Option<(Vec<RGB8>, u32, u32)> {
+        if !self.is_cell_lattice() {
+            return None;
+        }
+
+        let (cols, rows) = self.cell_dimensions();
+        let (size, thickness) = (widen(corridor_px), widen(wall_px));
+        let pitch = size + thickness;
+        let (image_width, image_height) = self.thin_wall_dimensions(corridor_px, wall_px);
+        let (width, height) = (widen(image_width), widen(image_height));
+
+        let wall_colour: RGB8 = (&TileState::Wall).into();
+        let mut pixels = vec![wall_colour; width * height];
+        let mut fill = |left: usize, top: usize, w: usize, h: usize, colour: RGB8| {
+            // an empty image has no rows to fill, and chunks can't be empty
+            if width == 0 {
+                return;
+            }
+            for row in pixels[top * width..].chunks_mut(width).take(h) {
+                row[left..left + w].fill(colour);
+            }
+        };
+
+        let walls = self.cell_walls();
+        for cy in 0..rows {
+            for cx in 0..cols {
+                let cell = Coord(cx * 2, cy * 2);
+                let (left, top) = (thickness + widen(cx) * pitch, thickness + widen(cy) * pitch);
+                fill(left, top, size, size, self.tile_colour(cell));
+
+                // the east-west passage under a crossing shows as a break in the walls either side
+                if self.data.at(cell) == Some(&TileState::Crossing) && size > 2 * thickness {
+                    fill(left, top, thickness, size, wall_colour);
+                    fill(left + size - thickness, top, thickness, size, wall_colour);
+                }
+
+                // each gap is shared by two cells, so only the east / north ones are drawn
+                let sides = walls.at(Coord(cx, cy)).copied().unwrap_or(0);
+                if sides & WALL_EAST == 0 {
+                    fill(
+                        left + size,
+                        top,
+                        thickness,
+                        size,
+                        self.tile_colour(Coord(cell.0 + 1, cell.1)),
+                    );
+                }
+                if sides & WALL_NORTH == 0 {
+                    fill(
+                        left,
+                        top + size,
+                        size,
+                        thickness,
+                        self.tile_colour(Coord(cell.0, cell.1 + 1)),
+                    );
+                }
+            }
+        }
+
+        Some((pixels, image_width, image_height))
+    }
+
+    /// The size in pixels of the image produced by `render_thin_walls`, for a maze `cols` x
+    /// `rows` cells across that is `cols * corridor_px + (cols + 1) * wall_px` pixels wide and
+    /// `rows * corridor_px + (rows + 1) * wall_px` pixels high.
+    ///
+    /// ```
+    /// use labyrinth::Maze;
+    ///
+    /// // 11 x 7 tiles is 6 x 4 cells
+    /// let maze = Maze::new(11, 7);
+    /// assert_eq!(maze.thin_wall_dimensions(8, 1), (6 * 8 + 7, 4 * 8 + 5));
+    /// assert_eq!(maze.thin_wall_dimensions(8, 0), (48, 32));
+    /// ```
+    pub fn thin_wall_dimensions(&self, corridor_px: u32, wall_px: u32) -> (u32, u32) {
+        let (cols, rows) = self.cell_dimensions();
+        (
+            cols * corridor_px + (cols + 1) * wall_px,
+            rows * corridor_px + (rows + 1) * wall_px,
+        )
+    }
+
+    // the walls around every cell of the lattice, indexed by cell rather than tile.
+    // the edge of the grid always counts as a wall.
+    fn cell_walls(&self) -> Tiles<u8> {
+        let (cols, rows) = self.cell_dimensions();
+        let mut walls = Tiles::init(cols, rows, 0);
+
+        for cy in 0..rows {
+            for cx in 0..cols {
+                let cell = Coord(cx * 2, cy * 2);
+                let sides = [
+                    (Direction::North, WALL_NORTH),
+                    (Direction::East, WALL_EAST),
+                    (Direction::South, WALL_SOUTH),
+                    (Direction::West, WALL_WEST),
+                ]
+                .iter()
+                .filter(|&&(direction, _)| !self.is_joined(cell, direction))
+                .fold(0, |sides, &(_, bit)| sides | bit);
+
+                if let Some(tile) = walls.at_mut(Coord(cx, cy)) {
+                    *tile = sides;
+                }
+            }
+        }
+
+        walls
+    }
+
+    // whether every passage is a cell of the lattice, or a wall tile between two cells which
+    // are both passages. the tiles between four cells and past the last cell are never carved
+    fn is_cell_lattice(&self) -> bool {
+        self.positions()
+            .filter(|&tile| self.is_passage(tile))
+            .all(|Coord(x, y)| {
+                let joins = |a: Option<Coord>, b: Option<Coord>| {
+                    a.zip(b)
+                        .is_some_and(|(a, b)| self.is_passage(a) && self.is_passage(b))
+                };
+                match (x % 2, y % 2) {
+                    (0, 0) => true,
+                    (1, 0) => joins(x.checked_sub(1).map(|x| Coord(x, y)), Some(Coord(x + 1, y))),
+                    (0, 1) => joins(y.checked_sub(1).map(|y| Coord(x, y)), Some(Coord(x, y + 1))),
+                    _ => false,
+                }
+            })
+    }
+
+    fn tile_colour(&self, tile: Coord) -> RGB8 {
+        (&self.data.at(tile).copied().unwrap_or_default()).into()
+    }
+}
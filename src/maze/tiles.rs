@@ -0,0 +1,113 @@
+use alloc::{vec, vec::Vec};
+use core::convert::TryFrom;
+
+#[cfg(feature = "std")]
+use grid::Grid;
+
+use super::{coord::widen, Coord};
+
+// a width x height grid of values indexed by (x, y), with (0, 0) in the top left. the values
+// are stored a row at a time so each row of the maze is contiguous, in the same order as the
+// pixels of the image. everything outside of the grid is None rather than a panic
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Tiles<T> {
+    width: u32,
+    height: u32,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Tiles<T> {
+    // panics if either the width or height is zero, there is no such thing as an empty maze
+    pub(crate) fn init(width: u32, height: u32, value: T) -> Self {
+        assert!(
+            width > 0 && height > 0,
+            "Cannot make a {}x{} grid of tiles, the width and height must both be at least 1.",
+            width,
+            height
+        );
+
+        Self {
+            width,
+            height,
+            cells: vec![value; widen(width) * widen(height)],
+        }
+    }
+
+    // the public API still speaks in terms of grid::Grid, which the crate indexes by (x, y) too.
+    // `None` if the grid is too big to be the tiles of a maze
+    #[cfg(feature = "std")]
+    pub(crate) fn from_grid(grid: &Grid<T>) -> Option<Self> {
+        let (width, height) = grid.size();
+        let (width, height) = (u32::try_from(width).ok()?, u32::try_from(height).ok()?);
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| Coord(x, y)))
+            .filter_map(|Coord(x, y)| grid.get(widen(x), widen(y)).cloned())
+            .collect();
+
+        Some(Self {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    #[cfg(feature = "std")]
+    pub(crate) fn to_grid(&self) -> Grid<T> {
+        // Grid stores x major, so the rows of the Vec handed to it are the columns of the maze
+        let cells = (0..self.width)
+            .flat_map(|x| (0..self.height).map(move |y| Coord(x, y)))
+            .filter_map(|tile| self.at(tile).cloned())
+            .collect();
+
+        Grid::from_vec(cells, widen(self.height))
+    }
+}
+
+impl<T: Clone + Default> Tiles<T> {
+    pub(crate) fn new(width: u32, height: u32) -> Self {
+        Self::init(width, height, T::default())
+    }
+}
+
+impl<T> Tiles<T> {
+    pub(crate) fn dimensions(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    // the value at a tile given as a pair of usizes, for the public API
+    pub(crate) fn get(&self, x: usize, y: usize) -> Option<&T> {
+        self.at(Coord::try_from((x, y)).ok()?)
+    }
+
+    pub(crate) fn at(&self, tile: Coord) -> Option<&T> {
+        let index = self.index(tile)?;
+        self.cells.get(index)
+    }
+
+    pub(crate) fn at_mut(&mut self, tile: Coord) -> Option<&mut T> {
+        let index = self.index(tile)?;
+        self.cells.get_mut(index)
+    }
+
+    // a grid of the same size with `f` applied to every value
+    pub(crate) fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Tiles<U> {
+        Tiles {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    // every value in row order, left to right along the top row first
+    pub(crate) fn iter(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    fn index(&self, Coord(x, y): Coord) -> Option<usize> {
+        if x < self.width && y < self.height {
+            Some(widen(y) * widen(self.width) + widen(x))
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use super::{row_major, Coord, Maze, Tiles};
+
+impl Maze {
+    /// The maze turned a quarter turn clockwise, so it is as wide as this one is tall. The start
+    /// and end turn with it.
+    ///
+    /// Every route through the maze is kept, but a crossing always carries the north-south
+    /// passage over the east-west one, so turning a weave maze swaps which passage goes over
+    /// and a route through a crossing can come out a tile longer or shorter.
+    ///
+    /// ```
+    /// use labyrinth::{Maze, MazeBuilder};
+    ///
+    /// let maze = MazeBuilder::new().width(31).height(17).seed(2).build();
+    /// let turned = maze.rotate_90();
+    /// assert_eq!(turned.dimensions(), (17, 31));
+    ///
+    /// // the top left corner ends up in the top right
+    /// let (x, y) = maze.start().unwrap();
+    /// assert_eq!(turned.start(), Some((16 - y, x)));
+    ///
+    /// let length = |maze: &Maze| maze.solution().map(|path| path.len());
+    /// assert_eq!(length(&turned), length(&maze));
+    /// assert_eq!(turned.rotate_90().rotate_90().rotate_90(), maze);
+    /// ```
+    pub fn rotate_90(&self) -> Maze {
+        let last_row = self.height - 1;
+        self.transformed(self.height, self.width, |Coord(x, y)| {
+            Coord(y, last_row - x)
+        })
+    }
+
+    /// The maze mirrored left to right, with the start and end mirrored along with it.
+    ///
+    /// ```
+    /// use labyrinth::{Maze, MazeBuilder};
+    ///
+    /// let maze = MazeBuilder::new().width(31).height(17).seed(2).build();
+    /// let mirrored = maze.flip_horizontal();
+    /// let (x, y) = maze.end().unwrap();
+    /// assert_eq!(mirrored.end(), Some((30 - x, y)));
+    ///
+    /// let length = |maze: &Maze| maze.solution().map(|path| path.len());
+    /// assert_eq!(length(&mirrored), length(&maze));
+    /// assert_eq!(mirrored.flip_horizontal(), maze);
+    /// ```
+    pub fn flip_horizontal(&self) -> Maze {
+        let last_column = self.width - 1;
+        self.transformed(self.width, self.height, |Coord(x, y)| {
+            Coord(last_column - x, y)
+        })
+    }
+
+    /// The maze mirrored top to bottom, with the start and end mirrored along with it.
+    ///
+    /// ```
+    /// use labyrinth::{Maze, MazeBuilder};
+    ///
+    /// let maze = MazeBuilder::new().width(31).height(17).seed(2).build();
+    /// let mirrored = maze.flip_vertical();
+    /// let (x, y) = maze.end().unwrap();
+    /// assert_eq!(mirrored.end(), Some((x, 16 - y)));
+    ///
+    /// let length = |maze: &Maze| maze.solution().map(|path| path.len());
+    /// assert_eq!(length(&mirrored), length(&maze));
+    /// assert_eq!(mirrored.flip_vertical(), maze);
+    /// ```
+    pub fn flip_vertical(&self) -> Maze {
+        let last_row = self.height - 1;
+        self.transformed(self.width, self.height, |Coord(x, y)| {
+            Coord(x, last_row - y)
+        })
+    }
+
+    // a finished `width` x `height` maze where each tile is copied from the tile `source` gives
+    // for it in this maze, the mask goes along with the tiles
+    fn transformed<F>(&self, width: u32, height: u32, source: F) -> Maze
+    where
+        F: Fn(Coord) -> Coord,
+    {
+        let mut maze = Maze::from_tiles(width, height, copy(&self.data, width, height, &source));
+        maze.mask = copy(&self.mask, width, height, &source);
+        maze.wrap = self.wrap;
+        maze
+    }
+}
+
+// a `width` x `height` grid with each value copied from where `source` maps it back to
+fn copy<T, F>(tiles: &Tiles<T>, width: u32, height: u32, source: &F) -> Tiles<T>
+where
+    T: Copy + Default,
+    F: Fn(Coord) -> Coord,
+{
+    let mut copied = Tiles::new(width, height);
+    for tile in row_major(width, height) {
+        if let (Some(to), Some(&from)) = (copied.at_mut(tile), tiles.at(source(tile))) {
+            *to = from;
+        }
+    }
+    copied
+}
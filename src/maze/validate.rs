@@ -0,0 +1,82 @@
+use core::{error::Error, fmt};
+
+use super::{Maze, TileState};
+
+/// Why `Maze::validate` turned a maze down.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum ValidationError {
+    /// There is no start tile.
+    NoStart,
+    /// There is no end tile.
+    NoEnd,
+    /// There is more than one start tile, holding how many there are.
+    MultipleStarts(usize),
+    /// There is more than one end tile, holding how many there are.
+    MultipleEnds(usize),
+    /// The passages don't lead from the start to the end.
+    EndUnreachable,
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ValidationError::NoStart => write!(f, "The maze has no start."),
+            ValidationError::NoEnd => write!(f, "The maze has no end."),
+            ValidationError::MultipleStarts(count) => {
+                write!(f, "The maze has {} starts, it needs exactly one.", count)
+            }
+            ValidationError::MultipleEnds(count) => {
+                write!(f, "The maze has {} ends, it needs exactly one.", count)
+            }
+            ValidationError::EndUnreachable => {
+                write!(f, "The end of the maze can't be reached from the start.")
+            }
+        }
+    }
+}
+
+impl Error for ValidationError {}
+
+impl Maze {
+    /// Checks the maze can be played, for mazes loaded from a file rather than generated. It
+    /// needs exactly one start and one end with a route along the passages between them.
+    ///
+    /// The start and end are passage tiles themselves, so there is no way for one to be stuck
+    /// inside a wall. One boxed in by walls is caught by the end being unreachable instead.
+    ///
+    #[cfg_attr(feature = "std", doc = "```")]
+    #[cfg_attr(not(feature = "std"), doc = "```ignore")]
+    /// use labyrinth::{Maze, ValidationError};
+    ///
+    /// assert_eq!(Maze::from_ascii("S  \n## \nE  ")?.validate(), Ok(()));
+    ///
+    /// let no_start = Maze::from_ascii("   \n## \nE  ")?;
+    /// assert_eq!(no_start.validate(), Err(ValidationError::NoStart));
+    ///
+    /// let two_ends = Maze::from_ascii("S E\n## \nE  ")?;
+    /// assert_eq!(two_ends.validate(), Err(ValidationError::MultipleEnds(2)));
+    ///
+    /// let walled_in = Maze::from_ascii("S  \n###\nE  ")?;
+    /// assert_eq!(walled_in.validate(), Err(ValidationError::EndUnreachable));
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let count = |state| self.data.iter().filter(|&&tile| tile == state).count();
+
+        match count(TileState::Start) {
+            0 => return Err(ValidationError::NoStart),
+            1 => {}
+            starts => return Err(ValidationError::MultipleStarts(starts)),
+        }
+        match count(TileState::End) {
+            0 => return Err(ValidationError::NoEnd),
+            1 => {}
+            ends => return Err(ValidationError::MultipleEnds(ends)),
+        }
+
+        match self.solution() {
+            Some(_) => Ok(()),
+            None => Err(ValidationError::EndUnreachable),
+        }
+    }
+}
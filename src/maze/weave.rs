@@ -0,0 +1,84 @@
+use alloc::{vec, vec::Vec};
+
+use rand::{prelude::SliceRandom, Rng};
+
+use super::{Coord, Direction, Maze, TileState};
+
+impl Maze {
+    /// Generates a weave maze using a randomized depth first search which can also tunnel east
+    /// or west underneath a straight north-south corridor, leaving a `TileState::Crossing` where
+    /// the two passages pass over each other.
+    pub fn populate_weave<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        let start = self.random_cell(rng);
+        self.carve(start);
+        self.visit(start);
+
+        let mut stack = vec![start];
+        while let Some(&cell) = stack.last() {
+            // every way out of the cell, paired with whether it tunnels under the next cell
+            let moves: Vec<_> = Direction::ALL
+                .iter()
+                .filter_map(|&direction| {
+                    let (next, _) = self.cell_towards(cell, direction)?;
+                    if !self.is_visited(next) {
+                        Some((direction, false))
+                    } else if self.can_tunnel(cell, direction) {
+                        Some((direction, true))
+                    } else {
+                        None
+                    }
+                })
+                .collect();
+
+            let Some(&(direction, tunnel)) = moves.choose(rng) else {
+                stack.pop();
+                continue;
+            };
+
+            let Some((mut next, wall)) = self.cell_towards(cell, direction) else {
+                continue;
+            };
+            self.carve(wall);
+
+            if tunnel {
+                let Some((beyond, far_wall)) = self.cell_towards(next, direction) else {
+                    continue;
+                };
+                self.set_tile(next, TileState::Crossing);
+                self.carve(far_wall);
+                next = beyond;
+            }
+
+            self.carve(next);
+            self.visit(next);
+            stack.push(next);
+        }
+
+        self.place_start_end();
+    }
+
+    // a cell can tunnel east or west under the next cell along if it is the middle of a straight
+    // north-south corridor and the cell on the far side of it hasn't been visited yet
+    fn can_tunnel(&self, cell: Coord, direction: Direction) -> bool {
+        if !matches!(direction, Direction::East | Direction::West) {
+            return false;
+        }
+
+        let Some((over, wall)) = self.cell_towards(cell, direction) else {
+            return false;
+        };
+        let Some((beyond, far_wall)) = self.cell_towards(over, direction) else {
+            return false;
+        };
+        let is_open = |direction| {
+            self.step(over, direction)
+                .is_some_and(|t| self.is_open_between(over, t))
+        };
+        self.data.at(over) == Some(&TileState::Empty)
+            && is_open(Direction::North)
+            && is_open(Direction::South)
+            && !self.is_passage(wall)
+            && !self.is_passage(far_wall)
+            && !self.is_visited(beyond)
+    }
+}
@@ -0,0 +1,342 @@
+//! Mazes stacked up in layers, joined together by stairs.
+//!
+//! Every layer is a grid of tiles laid out like a `Maze`, with the cells on the tiles with even
+//! coordinates and the walls between them on the odd ones. Tiles are addressed by `(x, y, z)`
+//! where `z` is the layer, counting up from 0 at the bottom. A stair joins a cell straight up
+//! to the same cell on the layer above, and can be walked both up and down.
+
+use std::collections::VecDeque;
+#[cfg(feature = "render")]
+use std::ffi::OsStr;
+
+#[cfg(feature = "render")]
+use anyhow::Context;
+use grid::Grid;
+use rand::{prelude::SliceRandom, Rng};
+#[cfg(feature = "render")]
+use rgb::ComponentBytes;
+use rgb::RGB8;
+
+#[cfg(feature = "render")]
+use crate::maze::{create_file, encode_png};
+use crate::{maze::gen_index, TileState};
+
+/// A maze of several layers which are joined together by stairs.
+///
+/// The start is the top left cell of the bottom layer and the end is the bottom right cell of
+/// the top layer.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Maze3D {
+    width: u32,
+    height: u32,
+    // the tiles of every layer, bottom layer first
+    layers: Vec<Grid<TileState>>,
+    // a tile of stairs[z] is set where a stair climbs from layer z to layer z + 1
+    stairs: Vec<Grid<bool>>,
+}
+
+impl Maze3D {
+    /// Creates a maze `depth` layers deep with every layer `width` x `height` tiles of solid wall.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the width, height or depth is zero.
+    pub fn new(width: u32, height: u32, depth: u32) -> Self {
+        assert!(
+            width > 0 && height > 0 && depth > 0,
+            "Cannot create a {}x{}x{} maze, the width, height and depth must all be at least 1.",
+            width,
+            height,
+            depth
+        );
+
+        let (w, h) = (width as usize, height as usize);
+        Self {
+            width,
+            height,
+            layers: vec![Grid::new(w, h); depth as usize],
+            stairs: vec![Grid::init(w, h, false); depth as usize - 1],
+        }
+    }
+
+    /// The width and height of each layer in tiles, and the number of layers.
+    pub fn dimensions(&self) -> (u32, u32, u32) {
+        (self.width, self.height, self.layers.len() as u32)
+    }
+
+    /// The tile at `(x, y, z)`, `None` if it is outside of the maze.
+    pub fn tile(&self, (x, y, z): (usize, usize, usize)) -> Option<TileState> {
+        self.layers.get(z)?.get(x, y).copied()
+    }
+
+    /// Whether a stair climbs from the tile at `(x, y, z)` up to the layer above.
+    pub fn has_stair(&self, (x, y, z): (usize, usize, usize)) -> bool {
+        self.stairs
+            .get(z)
+            .is_some_and(|stairs| stairs.get(x, y) == Some(&true))
+    }
+
+    /// The passage tiles which can be stepped to from the tile at `(x, y, z)`, the four around
+    /// it on the same layer along with the tiles at either end of any stairs. Walls have no
+    /// open neighbours.
+    pub fn open_neighbours(&self, tile: (usize, usize, usize)) -> Vec<(usize, usize, usize)> {
+        if !self.is_passage(tile) {
+            return Vec::new();
+        }
+
+        let (x, y, z) = tile;
+        let mut neighbours = Vec::new();
+        if x > 0 {
+            neighbours.push((x - 1, y, z));
+        }
+        if y > 0 {
+            neighbours.push((x, y - 1, z));
+        }
+        neighbours.push((x + 1, y, z));
+        neighbours.push((x, y + 1, z));
+
+        neighbours.retain(|&neighbour| self.is_passage(neighbour));
+        if self.has_stair(tile) {
+            neighbours.push((x, y, z + 1));
+        }
+        if z > 0 && self.has_stair((x, y, z - 1)) {
+            neighbours.push((x, y, z - 1));
+        }
+
+        neighbours
+    }
+
+    /// Generates the maze using a randomized depth first search, which treats going up and down
+    /// a layer as two more directions alongside the usual four.
+    ///
+    /// ```
+    /// use labyrinth::Maze3D;
+    /// use rand::{rngs::SmallRng, SeedableRng};
+    ///
+    /// let mut maze = Maze3D::new(9, 9, 2);
+    /// maze.populate(&mut SmallRng::seed_from_u64(1));
+    ///
+    /// let mut tiles = (0..9).flat_map(|y| (0..9).map(move |x| (x, y, 0)));
+    /// assert!(tiles.any(|tile| maze.has_stair(tile)));
+    ///
+    /// // the start is on the bottom layer and the end on the top one, so the path climbs a stair
+    /// let path = maze.solve_bfs().unwrap();
+    /// assert_eq!(path.first(), Some(&maze.start()));
+    /// assert_eq!(path.last(), Some(&maze.end()));
+    /// ```
+    pub fn populate<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        let (cols, rows) = self.cell_dimensions();
+        let start = (
+            gen_index(rng, 0..cols) * 2,
+            gen_index(rng, 0..rows) * 2,
+            gen_index(rng, 0..self.layers.len()),
+        );
+
+        // indexed by cell rather than tile
+        let mut visited: Vec<Grid<bool>> = vec![Grid::init(cols, rows, false); self.layers.len()];
+        visit(&mut visited, start);
+        self.carve(start);
+
+        let mut stack = vec![start];
+        while let Some(&cell) = stack.last() {
+            let unvisited: Vec<_> = self
+                .cell_neighbours(cell)
+                .into_iter()
+                .filter(|&((x, y, z), _)| visited[z].get(x / 2, y / 2) == Some(&false))
+                .collect();
+
+            if let Some(&(next, wall)) = unvisited.choose(rng) {
+                match wall {
+                    Some(wall) => self.carve(wall),
+                    None => self.build_stair(cell, next),
+                }
+                self.carve(next);
+                visit(&mut visited, next);
+                stack.push(next);
+            } else {
+                stack.pop();
+            }
+        }
+
+        self.set_tile(self.start(), TileState::Start);
+        if self.end() != self.start() {
+            self.set_tile(self.end(), TileState::End);
+        }
+    }
+
+    /// The cell at the top left corner of the bottom layer.
+    pub fn start(&self) -> (usize, usize, usize) {
+        (0, 0, 0)
+    }
+
+    /// The cell at the bottom right corner of the top layer.
+    pub fn end(&self) -> (usize, usize, usize) {
+        let (cols, rows) = self.cell_dimensions();
+        ((cols - 1) * 2, (rows - 1) * 2, self.layers.len() - 1)
+    }
+
+    /// Finds the shortest path from the start to the end using a breadth first search.
+    ///
+    /// The path includes both the start and end tiles, `None` is returned
+    /// if the end can't be reached.
+    pub fn solve_bfs(&self) -> Option<Vec<(usize, usize, usize)>> {
+        let (start, end) = (self.start(), self.end());
+        if !self.is_passage(start) {
+            return None;
+        }
+
+        // the tile each tile was first reached from, the start points at itself
+        let mut previous: Vec<Grid<Option<(usize, usize, usize)>>> =
+            vec![Grid::new(self.width as usize, self.height as usize); self.layers.len()];
+        *previous[start.2].get_mut(start.0, start.1)? = Some(start);
+
+        let mut queue = VecDeque::from(vec![start]);
+        while let Some(tile) = queue.pop_front() {
+            if tile == end {
+                break;
+            }
+
+            for neighbour in self.open_neighbours(tile) {
+                let (x, y, z) = neighbour;
+                if let Some(prev @ None) = previous[z].get_mut(x, y) {
+                    *prev = Some(tile);
+                    queue.push_back(neighbour);
+                }
+            }
+        }
+
+        // walk back from the end following the previous pointers
+        let mut path = vec![end];
+        let mut tile = end;
+        while tile != start {
+            tile = previous[tile.2].get(tile.0, tile.1).copied().flatten()?;
+            path.push(tile);
+        }
+
+        path.reverse();
+        Some(path)
+    }
+
+    /// Renders a layer to pixels in row order, one pixel per tile, `None` if there is no such
+    /// layer.
+    ///
+    /// Tiles are drawn in the usual colours, except for the ends of stairs. Stairs going up are
+    /// blue, stairs going down are orange, and purple tiles have stairs going both ways.
+    pub fn render_layer(&self, z: usize) -> Option<Vec<RGB8>> {
+        let layer = self.layers.get(z)?;
+
+        let pixels = (0..self.height as usize)
+            .flat_map(|y| (0..self.width as usize).map(move |x| (x, y)))
+            .map(|(x, y)| {
+                let tile = layer.get(x, y).copied().unwrap_or_default();
+                let up = self.has_stair((x, y, z));
+                let down = z > 0 && self.has_stair((x, y, z - 1));
+                match (tile, up, down) {
+                    (TileState::Start | TileState::End, _, _) | (_, false, false) => (&tile).into(),
+                    (_, true, false) => STAIR_UP_COLOUR,
+                    (_, false, true) => STAIR_DOWN_COLOUR,
+                    (_, true, true) => STAIR_BOTH_COLOUR,
+                }
+            })
+            .collect();
+
+        Some(pixels)
+    }
+
+    /// Saves every layer as a PNG named after `prefix` and the layer number, so a prefix of
+    /// `layer` gives `layer_0.png`, `layer_1.png` and so on. See `render_layer`.
+    #[cfg(feature = "render")]
+    pub fn save_layers<S: AsRef<OsStr> + ?Sized>(&self, prefix: &S) -> anyhow::Result<()> {
+        let prefix = prefix.as_ref().to_string_lossy();
+
+        for z in 0..self.layers.len() {
+            let path = format!("{}_{}.png", prefix, z);
+            let pixels = self
+                .render_layer(z)
+                .with_context(|| format!("The maze has no layer {}.", z))?;
+            encode_png(
+                create_file(&path)?,
+                self.width,
+                self.height,
+                png::ColorType::RGB,
+                pixels.as_bytes(),
+            )?;
+        }
+
+        Ok(())
+    }
+
+    // the number of cells across and down each layer, cells sit on the even tiles
+    fn cell_dimensions(&self) -> (usize, usize) {
+        (
+            (self.width as usize).div_ceil(2),
+            (self.height as usize).div_ceil(2),
+        )
+    }
+
+    // the neighbouring cells of a cell, paired with the wall tile between them on the same
+    // layer. cells on the layers above and below have a stair instead of a wall
+    fn cell_neighbours(&self, (x, y, z): Tile) -> Vec<(Tile, Option<Tile>)> {
+        let (width, height) = (self.width as usize, self.height as usize);
+
+        let mut neighbours = Vec::new();
+        if y + 2 < height {
+            neighbours.push(((x, y + 2, z), Some((x, y + 1, z))));
+        }
+        if x + 2 < width {
+            neighbours.push(((x + 2, y, z), Some((x + 1, y, z))));
+        }
+        if y >= 2 {
+            neighbours.push(((x, y - 2, z), Some((x, y - 1, z))));
+        }
+        if x >= 2 {
+            neighbours.push(((x - 2, y, z), Some((x - 1, y, z))));
+        }
+        if z + 1 < self.layers.len() {
+            neighbours.push(((x, y, z + 1), None));
+        }
+        if z > 0 {
+            neighbours.push(((x, y, z - 1), None));
+        }
+
+        neighbours
+    }
+
+    // a stair is stored on the lower of the two layers it joins
+    fn build_stair(&mut self, (x, y, a): (usize, usize, usize), (_, _, b): (usize, usize, usize)) {
+        if let Some(stair) = self.stairs.get_mut(a.min(b)).and_then(|s| s.get_mut(x, y)) {
+            *stair = true;
+        }
+    }
+
+    fn carve(&mut self, tile: (usize, usize, usize)) {
+        self.set_tile(tile, TileState::Empty);
+    }
+
+    fn set_tile(&mut self, (x, y, z): (usize, usize, usize), state: TileState) {
+        if let Some(tile) = self.layers.get_mut(z).and_then(|layer| layer.get_mut(x, y)) {
+            *tile = state;
+        }
+    }
+
+    fn is_passage(&self, tile: (usize, usize, usize)) -> bool {
+        !matches!(self.tile(tile), Some(TileState::Wall) | None)
+    }
+}
+
+// a tile's x, y and layer
+type Tile = (usize, usize, usize);
+
+fn visit(visited: &mut [Grid<bool>], (x, y, z): (usize, usize, usize)) {
+    if let Some(cell) = visited
+        .get_mut(z)
+        .and_then(|layer| layer.get_mut(x / 2, y / 2))
+    {
+        *cell = true;
+    }
+}
+
+// the colours of the ends of the stairs when rendering a layer
+const STAIR_UP_COLOUR: RGB8 = RGB8::new(0x1E_u8, 0x90_u8, 0xFF_u8);
+const STAIR_DOWN_COLOUR: RGB8 = RGB8::new(0xFF_u8, 0x8C_u8, 0x00_u8);
+const STAIR_BOTH_COLOUR: RGB8 = RGB8::new(0x99_u8, 0x32_u8, 0xCC_u8);
@@ -0,0 +1,334 @@
+//! Circular mazes made of concentric rings of cells.
+//!
+//! Cells are addressed by `(ring, index)`, ring 0 is the single cell in the centre and the index
+//! counts clockwise around the ring from the positive x axis. Rings are split into roughly square
+//! cells, so every ring either has the same number of cells as the one inside it, or some whole
+//! multiple of them (normally twice as many).
+
+use std::{f64::consts::PI, ffi::OsStr, fmt::Write, fs, path::Path};
+
+use anyhow::Context;
+use rand::{prelude::SliceRandom, Rng};
+
+use crate::{
+    maze::{gen_index, hex_colour},
+    TileState,
+};
+
+/// A maze on a disc of concentric rings.
+///
+/// The start is the cell in the centre and the end is the first cell of the outermost ring.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct PolarMaze {
+    // the passages out of each cell, as INWARD / CLOCKWISE bits. outward and counter clockwise
+    // passages are stored on the cell at the other end so each passage only has one bit
+    links: Vec<Vec<u8>>,
+}
+
+impl PolarMaze {
+    /// Creates a maze with `rings` rings, counting the centre cell, with every wall in place.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rings` is zero.
+    ///
+    /// ```
+    /// use labyrinth::PolarMaze;
+    ///
+    /// let maze = PolarMaze::new(3);
+    /// assert_eq!(maze.rings(), 3);
+    /// assert_eq!(
+    ///     (0..3).map(|ring| maze.cells_in_ring(ring)).collect::<Vec<_>>(),
+    ///     [1, 6, 12]
+    /// );
+    ///
+    /// // the centre opens onto all of the first ring, which each have two cells outside them
+    /// assert_eq!(maze.neighbours((0, 0)).len(), 6);
+    /// assert_eq!(maze.neighbours((1, 0)).len(), 5);
+    /// assert_eq!(maze.neighbours((2, 0)).len(), 3);
+    /// assert!(maze.open_neighbours((1, 0)).is_empty());
+    /// ```
+    pub fn new(rings: usize) -> Self {
+        assert!(rings > 0, "Cannot create a polar maze without any rings.");
+
+        let mut links = vec![vec![0_u8]];
+        for ring in 1..rings {
+            let previous = links[ring - 1].len();
+
+            // split the ring up so its cells are about as wide as they are deep
+            let circumference = 2.0 * PI * ring as f64;
+            let ratio = (circumference / previous as f64).round().max(1.0) as usize;
+            links.push(vec![0; previous * ratio]);
+        }
+
+        Self { links }
+    }
+
+    /// The number of rings, counting the centre cell.
+    pub fn rings(&self) -> usize {
+        self.links.len()
+    }
+
+    /// The number of cells in a ring, 0 if the ring is outside of the maze.
+    pub fn cells_in_ring(&self, ring: usize) -> usize {
+        self.links.get(ring).map_or(0, Vec::len)
+    }
+
+    /// Whether the cell is inside the maze.
+    pub fn contains(&self, (ring, index): (usize, usize)) -> bool {
+        index < self.cells_in_ring(ring)
+    }
+
+    /// The cells next to a cell in a direction, going outward there can be several.
+    pub fn neighbours_towards(
+        &self,
+        (ring, index): (usize, usize),
+        direction: PolarDirection,
+    ) -> Vec<(usize, usize)> {
+        let count = self.cells_in_ring(ring);
+        if index >= count {
+            return Vec::new();
+        }
+
+        match direction {
+            PolarDirection::Inward if ring > 0 => {
+                let ratio = count / self.cells_in_ring(ring - 1);
+                vec![(ring - 1, index / ratio)]
+            }
+            PolarDirection::Outward if ring + 1 < self.rings() => {
+                // each cell has the same number of cells outside of it, a run of `ratio` cells
+                let ratio = self.cells_in_ring(ring + 1) / count;
+                (index * ratio..(index + 1) * ratio)
+                    .map(|outer| (ring + 1, outer))
+                    .collect()
+            }
+            // the centre is a single cell so it has no neighbours around the ring
+            PolarDirection::Clockwise if ring > 0 => vec![(ring, (index + 1) % count)],
+            PolarDirection::CounterClockwise if ring > 0 => {
+                vec![(ring, (index + count - 1) % count)]
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Every cell next to a cell, whether or not there is a wall in the way.
+    pub fn neighbours(&self, cell: (usize, usize)) -> Vec<(usize, usize)> {
+        PolarDirection::ALL
+            .iter()
+            .flat_map(|&direction| self.neighbours_towards(cell, direction))
+            .collect()
+    }
+
+    /// The neighbouring cells which can be reached without going through a wall.
+    pub fn open_neighbours(&self, cell: (usize, usize)) -> Vec<(usize, usize)> {
+        self.neighbours(cell)
+            .into_iter()
+            .filter(|&neighbour| self.is_linked(cell, neighbour))
+            .collect()
+    }
+
+    /// Whether there is a passage between two neighbouring cells.
+    pub fn is_linked(&self, a: (usize, usize), b: (usize, usize)) -> bool {
+        self.link_bit(a, b)
+            .is_some_and(|((ring, index), bit)| self.links[ring][index] & bit != 0)
+    }
+
+    /// Generates the maze using a randomized depth first search.
+    pub fn populate<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        let mut visited: Vec<Vec<bool>> = self
+            .links
+            .iter()
+            .map(|ring| vec![false; ring.len()])
+            .collect();
+
+        let ring = gen_index(rng, 0..self.rings());
+        let start = (ring, gen_index(rng, 0..self.cells_in_ring(ring)));
+        visited[start.0][start.1] = true;
+
+        let mut stack = vec![start];
+        while let Some(&cell) = stack.last() {
+            let unvisited: Vec<_> = self
+                .neighbours(cell)
+                .into_iter()
+                .filter(|&(ring, index)| !visited[ring][index])
+                .collect();
+
+            if let Some(&next) = unvisited.choose(rng) {
+                self.link(cell, next);
+                visited[next.0][next.1] = true;
+                stack.push(next);
+            } else {
+                stack.pop();
+            }
+        }
+    }
+
+    /// Saves the maze as an SVG image with rings 10 units deep, see `to_svg`.
+    pub fn save_svg<S: AsRef<OsStr> + ?Sized>(&self, s: &S) -> anyhow::Result<()> {
+        let path = Path::new(s);
+        fs::write(path, self.to_svg(POLAR_SVG_RING_WIDTH))
+            .with_context(|| format!("Failed to write the SVG to {}.", path.display()))
+    }
+
+    /// Renders the maze as an SVG image with every ring `ring_width` units deep.
+    ///
+    /// Walls between rings are drawn as arcs and walls between the cells of a ring as radial lines.
+    pub fn to_svg(&self, ring_width: u32) -> String {
+        let ring_width = f64::from(ring_width);
+        let margin = ring_width / 2.0;
+        let radius = ring_width * self.rings() as f64;
+        let size = (2.0 * (radius + margin)).ceil();
+        let centre = size / 2.0;
+
+        // the point at a distance from the centre and a fraction of the way around the circle
+        let point = |distance: f64, turns: f64| {
+            let angle = 2.0 * PI * turns;
+            (
+                centre + distance * angle.cos(),
+                centre + distance * angle.sin(),
+            )
+        };
+
+        let mut svg = String::new();
+        let _ = writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{s}" height="{s}" viewBox="0 0 {s} {s}">"#,
+            s = size,
+        );
+        let _ = writeln!(
+            svg,
+            r#"<rect width="{s}" height="{s}" fill="{}"/>"#,
+            hex_colour(&TileState::Empty),
+            s = size,
+        );
+
+        let _ = writeln!(
+            svg,
+            r#"<circle cx="{c:.2}" cy="{c:.2}" r="{:.2}" fill="{}"/>"#,
+            ring_width,
+            hex_colour(&TileState::Start),
+            c = centre,
+        );
+        if self.rings() > 1 {
+            let (inner, outer) = (radius - ring_width, radius);
+            let turns = 1.0 / self.cells_in_ring(self.rings() - 1) as f64;
+            let ((x1, y1), (x2, y2)) = (point(inner, 0.0), point(inner, turns));
+            let ((x3, y3), (x4, y4)) = (point(outer, turns), point(outer, 0.0));
+            let _ = writeln!(
+                svg,
+                r#"<path d="M {:.2} {:.2} A {r1:.2} {r1:.2} 0 0 1 {:.2} {:.2} L {:.2} {:.2} A {r2:.2} {r2:.2} 0 0 0 {:.2} {:.2} Z" fill="{}"/>"#,
+                x1,
+                y1,
+                x2,
+                y2,
+                x3,
+                y3,
+                x4,
+                y4,
+                hex_colour(&TileState::End),
+                r1 = inner,
+                r2 = outer,
+            );
+        }
+
+        let _ = writeln!(
+            svg,
+            r#"<g stroke="{}" stroke-width="{:.2}" stroke-linecap="round" fill="none">"#,
+            hex_colour(&TileState::Wall),
+            ring_width / 5.0
+        );
+        for (ring, cells) in self.links.iter().enumerate().skip(1) {
+            let (inner, outer) = (ring_width * ring as f64, ring_width * (ring + 1) as f64);
+            let turns = 1.0 / cells.len() as f64;
+
+            for (index, &bits) in cells.iter().enumerate() {
+                let (start, end) = (index as f64 * turns, (index + 1) as f64 * turns);
+
+                if bits & INWARD == 0 {
+                    let ((x1, y1), (x2, y2)) = (point(inner, start), point(inner, end));
+                    let _ = writeln!(
+                        svg,
+                        r#"<path d="M {:.2} {:.2} A {r:.2} {r:.2} 0 0 1 {:.2} {:.2}"/>"#,
+                        x1,
+                        y1,
+                        x2,
+                        y2,
+                        r = inner,
+                    );
+                }
+
+                if bits & CLOCKWISE == 0 {
+                    let ((x1, y1), (x2, y2)) = (point(inner, end), point(outer, end));
+                    let _ = writeln!(
+                        svg,
+                        r#"<line x1="{:.2}" y1="{:.2}" x2="{:.2}" y2="{:.2}"/>"#,
+                        x1, y1, x2, y2
+                    );
+                }
+            }
+        }
+        let _ = writeln!(
+            svg,
+            r#"<circle cx="{c:.2}" cy="{c:.2}" r="{:.2}"/>"#,
+            radius,
+            c = centre,
+        );
+        svg.push_str("</g>\n</svg>\n");
+
+        svg
+    }
+
+    // the cell and bit which record the passage between two neighbouring cells
+    fn link_bit(&self, a: (usize, usize), b: (usize, usize)) -> Option<((usize, usize), u8)> {
+        if !self.contains(a) || !self.contains(b) {
+            return None;
+        }
+
+        let inward_of = |cell| self.neighbours_towards(cell, PolarDirection::Inward);
+        let clockwise_of = |cell| self.neighbours_towards(cell, PolarDirection::Clockwise);
+
+        if inward_of(a) == [b] {
+            Some((a, INWARD))
+        } else if inward_of(b) == [a] {
+            Some((b, INWARD))
+        } else if clockwise_of(a) == [b] {
+            Some((a, CLOCKWISE))
+        } else if clockwise_of(b) == [a] {
+            Some((b, CLOCKWISE))
+        } else {
+            None
+        }
+    }
+
+    // knocks down the wall between two neighbouring cells
+    fn link(&mut self, a: (usize, usize), b: (usize, usize)) {
+        if let Some(((ring, index), bit)) = self.link_bit(a, b) {
+            self.links[ring][index] |= bit;
+        }
+    }
+}
+
+/// The directions from a cell to its neighbours, clockwise is the direction of increasing index.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum PolarDirection {
+    Inward,
+    Outward,
+    Clockwise,
+    CounterClockwise,
+}
+
+impl PolarDirection {
+    pub const ALL: [PolarDirection; 4] = [
+        PolarDirection::Inward,
+        PolarDirection::Outward,
+        PolarDirection::Clockwise,
+        PolarDirection::CounterClockwise,
+    ];
+}
+
+// the bits of a cell's links
+const INWARD: u8 = 1;
+const CLOCKWISE: u8 = 2;
+
+// the depth of a ring in SVG units when saving to a file
+const POLAR_SVG_RING_WIDTH: u32 = 10;
@@ -0,0 +1,56 @@
+use alloc::{vec, vec::Vec};
+
+/// A disjoint set forest over the indices `0..len`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct UnionFind {
+    parent: Vec<usize>,
+    rank: Vec<u8>,
+}
+
+impl UnionFind {
+    pub fn new(len: usize) -> Self {
+        Self {
+            parent: (0..len).collect(),
+            rank: vec![0; len],
+        }
+    }
+
+    /// Finds the representative of the set containing `x`, compressing the path as it goes.
+    pub fn find(&mut self, x: usize) -> usize {
+        let mut root = x;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+
+        // point everything on the path directly at the root
+        let mut node = x;
+        while self.parent[node] != root {
+            let next = self.parent[node];
+            self.parent[node] = root;
+            node = next;
+        }
+
+        root
+    }
+
+    /// Merges the sets containing `a` and `b`, returns false if they were already the same set.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let a = self.find(a);
+        let b = self.find(b);
+        if a == b {
+            return false;
+        }
+
+        // hang the shallower tree under the deeper one
+        match self.rank[a].cmp(&self.rank[b]) {
+            core::cmp::Ordering::Less => self.parent[a] = b,
+            core::cmp::Ordering::Greater => self.parent[b] = a,
+            core::cmp::Ordering::Equal => {
+                self.parent[b] = a;
+                self.rank[a] += 1;
+            }
+        }
+
+        true
+    }
+}